@@ -0,0 +1,119 @@
+use crate::{into_counters, CharsCounter, Map};
+use alloc::vec::Vec;
+
+/// Counts characters fed in pieces, such as chunks arriving from a socket.
+///
+/// ```
+/// use chars_counter::{CharsAccumulator, ICharsCounter};
+///
+/// let mut acc = CharsAccumulator::new();
+/// acc.feed("Hel");
+/// acc.feed("lo");
+/// assert_eq!(acc.finish(), "Hello".count_chars());
+/// ```
+pub struct CharsAccumulator<F = fn(&char) -> bool> {
+    counts: Map<char, usize>,
+    filter: Option<F>,
+}
+
+impl<F> Default for CharsAccumulator<F> {
+    fn default() -> Self {
+        Self {
+            counts: Map::new(),
+            filter: None,
+        }
+    }
+}
+
+impl CharsAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<F> CharsAccumulator<F>
+where
+    F: FnMut(&char) -> bool,
+{
+    /// Creates an accumulator that only counts the characters accepted by `predicate`.
+    pub fn with_filter(predicate: F) -> Self {
+        Self {
+            counts: Map::new(),
+            filter: Some(predicate),
+        }
+    }
+
+    pub fn feed(&mut self, s: &str) {
+        for c in s.chars() {
+            self.feed_char(c);
+        }
+    }
+
+    pub fn feed_char(&mut self, c: char) {
+        if self.filter.as_mut().is_none_or(|f| f(&c)) {
+            *self.counts.entry(c).or_insert(0) += 1;
+        }
+    }
+
+    /// Returns the counts so far, sorted like `count_chars`. Feeding may continue afterwards.
+    pub fn finish(&self) -> Vec<CharsCounter> {
+        into_counters(self.counts.clone())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{CharsAccumulator, ICharsCounter};
+
+    #[test]
+    fn feed_test() {
+        let str = "Hello, 世界!";
+        let mut acc = CharsAccumulator::new();
+        for chunk in ["Hel", "lo, ", "世", "界!"] {
+            acc.feed(chunk);
+        }
+        assert_eq!(acc.finish(), str.count_chars());
+
+        let mut acc = CharsAccumulator::new();
+        str.chars().for_each(|c| acc.feed_char(c));
+        assert_eq!(acc.finish(), str.count_chars());
+    }
+
+    #[test]
+    fn snapshot_test() {
+        let mut acc = CharsAccumulator::new();
+        acc.feed("aab");
+        assert_eq!(acc.finish(), "aab".count_chars());
+        acc.feed("bbc");
+        assert_eq!(acc.finish(), "aabbbc".count_chars());
+    }
+
+    #[test]
+    fn filter_test() {
+        let mut acc = CharsAccumulator::with_filter(|x| x.is_numeric());
+        acc.feed("a1b2");
+        acc.feed("½c3");
+        assert_eq!(acc.finish(), "a1b2½c3".count_chars_numeric());
+    }
+
+    #[test]
+    fn filter_rc_test() {
+        let seen = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = std::rc::Rc::clone(&seen);
+        let mut acc = CharsAccumulator::with_filter(move |x| {
+            counter.set(counter.get() + 1);
+            x.is_ascii()
+        });
+        acc.feed("aé");
+        assert_eq!(acc.finish(), "a".count_chars());
+        assert_eq!(seen.get(), 2);
+    }
+
+    #[test]
+    fn empty_test() {
+        assert!(CharsAccumulator::new().finish().is_empty());
+        let mut acc = CharsAccumulator::with_filter(|x| x.is_numeric());
+        acc.feed("abc");
+        assert!(acc.finish().is_empty());
+    }
+}
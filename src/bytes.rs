@@ -0,0 +1,174 @@
+use crate::counter::cmp_counters;
+use crate::{Counter, ICounterExt};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ByteCounter {
+    pub byte: u8,
+    pub count: usize,
+}
+
+impl Counter for ByteCounter {
+    type Key = u8;
+
+    fn key(&self) -> &u8 {
+        &self.byte
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl Ord for ByteCounter {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_counters(self, other)
+    }
+}
+
+impl PartialOrd for ByteCounter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub trait IBytesCounter {
+    fn count_bytes(&self) -> Vec<ByteCounter>;
+    fn count_bytes_filter<P>(&self, predicate: P) -> Vec<ByteCounter>
+    where
+        P: FnMut(&u8) -> bool;
+}
+
+/// Queries on the result of `count_bytes`, with `find_by_byte` taking the byte by value.
+pub trait IByteCounterExt {
+    fn most_bytes(&self) -> Vec<ByteCounter>;
+    fn least_bytes(&self) -> Vec<ByteCounter>;
+    fn find_by_byte(&self, b: u8) -> Option<ByteCounter>;
+}
+
+impl IBytesCounter for [u8] {
+    fn count_bytes(&self) -> Vec<ByteCounter> {
+        self.count_bytes_filter(|_| true)
+    }
+
+    fn count_bytes_filter<P>(&self, mut predicate: P) -> Vec<ByteCounter>
+    where
+        P: FnMut(&u8) -> bool,
+    {
+        let mut counts = [0usize; 256];
+        for b in self {
+            if predicate(b) {
+                counts[*b as usize] += 1;
+            }
+        }
+        let mut result = counts
+            .iter()
+            .enumerate()
+            .filter(|x| *x.1 > 0)
+            .map(|x| ByteCounter {
+                byte: x.0 as u8,
+                count: *x.1,
+            })
+            .collect::<Vec<_>>();
+        result.sort();
+        result
+    }
+}
+
+/// Counts the UTF-8 bytes of the string.
+impl IBytesCounter for str {
+    fn count_bytes(&self) -> Vec<ByteCounter> {
+        self.as_bytes().count_bytes()
+    }
+
+    fn count_bytes_filter<P>(&self, predicate: P) -> Vec<ByteCounter>
+    where
+        P: FnMut(&u8) -> bool,
+    {
+        self.as_bytes().count_bytes_filter(predicate)
+    }
+}
+
+impl IByteCounterExt for Vec<ByteCounter> {
+    fn most_bytes(&self) -> Vec<ByteCounter> {
+        self.most()
+    }
+
+    fn least_bytes(&self) -> Vec<ByteCounter> {
+        self.least()
+    }
+
+    fn find_by_byte(&self, b: u8) -> Option<ByteCounter> {
+        self.find_by_key(&b)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{ByteCounter, IByteCounterExt, IBytesCounter, ICounterExt};
+
+    #[test]
+    fn count_bytes_test() {
+        let buffer = vec![0xFFu8; 1000];
+        assert_eq!(
+            buffer.count_bytes(),
+            vec![ByteCounter {
+                byte: 0xFF,
+                count: 1000
+            }]
+        );
+
+        let buffer = (0..=255u8).rev().collect::<Vec<_>>();
+        let result = buffer.count_bytes();
+        assert_eq!(result.len(), 256);
+        assert!(result.iter().enumerate().all(|x| x.1.byte as usize == x.0));
+        assert_eq!(result.most_bytes().len(), 256);
+        assert_eq!(result.find_by_byte(0x80).unwrap().count, 1);
+
+        let result = buffer.count_bytes_filter(|x| x.is_ascii_digit());
+        assert_eq!(result.len(), 10);
+
+        let buffer: &[u8] = &[];
+        assert!(buffer.count_bytes().is_empty());
+        assert!(buffer.count_bytes().least_bytes().is_empty());
+    }
+
+    #[test]
+    fn byte_ext_test() {
+        let result = b"\x00\x01\x01\xfe\xfe\xfe".count_bytes();
+        assert_eq!(result.most_bytes()[0].byte, 0xFE);
+        assert_eq!(result.least_bytes()[0].byte, 0x00);
+        assert_eq!(result.find_by_num(2)[0].byte, 0x01);
+        assert!(result.find_by_byte(0x02).is_none());
+    }
+
+    #[test]
+    fn str_bytes_test() {
+        let result = "aabé".count_bytes();
+        assert_eq!(
+            result,
+            vec![
+                ByteCounter {
+                    byte: b'a',
+                    count: 2
+                },
+                ByteCounter {
+                    byte: b'b',
+                    count: 1
+                },
+                ByteCounter {
+                    byte: 0xA9,
+                    count: 1
+                },
+                ByteCounter {
+                    byte: 0xC3,
+                    count: 1
+                }
+            ]
+        );
+        assert_eq!(result, "aabé".as_bytes().count_bytes());
+        assert_eq!("aabé".count_bytes_filter(|x| x.is_ascii()).len(), 2);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::Map;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// A key paired with how often it was counted, like `WordCounter` or `ByteCounter`.
+///
+/// Counters are ordered by descending count, then ascending key, which is the order
+/// the counting functions return.
+pub trait Counter: Clone {
+    type Key: Ord + ?Sized;
+
+    fn key(&self) -> &Self::Key;
+    fn count(&self) -> usize;
+}
+
+/// The `Ord` of every `Counter`.
+pub(crate) fn cmp_counters<T: Counter>(a: &T, b: &T) -> Ordering {
+    b.count().cmp(&a.count()).then_with(|| a.key().cmp(b.key()))
+}
+
+/// Turns `counts` into counters built by `counter`, sorted in `Counter` order.
+pub(crate) fn into_sorted<K, T, F>(counts: Map<K, usize>, mut counter: F) -> Vec<T>
+where
+    T: Counter + Ord,
+    F: FnMut(K, usize) -> T,
+{
+    let mut result = counts
+        .into_iter()
+        .map(|(key, count)| counter(key, count))
+        .collect::<Vec<_>>();
+    result.sort();
+    result
+}
+
+/// Queries shared by the sorted results of every `Counter` type.
+pub trait ICounterExt<T: Counter> {
+    /// The counters tied for the highest count, empty for an empty vector.
+    fn most(&self) -> Vec<T>;
+    /// The counters tied for the lowest count, empty for an empty vector.
+    fn least(&self) -> Vec<T>;
+    fn find_by_num(&self, n: usize) -> Vec<T>;
+    fn find_by_key(&self, key: &T::Key) -> Option<T>;
+    fn counter_filter<P>(&self, predicate: P) -> Vec<T>
+    where
+        P: FnMut(&&T) -> bool;
+}
+
+impl<T: Counter> ICounterExt<T> for Vec<T> {
+    fn most(&self) -> Vec<T> {
+        match self.first() {
+            Some(first) => self.find_by_num(first.count()),
+            None => Vec::new(),
+        }
+    }
+
+    fn least(&self) -> Vec<T> {
+        match self.last() {
+            Some(last) => self.find_by_num(last.count()),
+            None => Vec::new(),
+        }
+    }
+
+    fn find_by_num(&self, n: usize) -> Vec<T> {
+        self.counter_filter(|x| x.count() == n)
+    }
+
+    fn find_by_key(&self, key: &T::Key) -> Option<T> {
+        self.iter().find(|x| x.key() == key).cloned()
+    }
+
+    fn counter_filter<P>(&self, predicate: P) -> Vec<T>
+    where
+        P: FnMut(&&T) -> bool,
+    {
+        self.iter().filter(predicate).cloned().collect::<Vec<_>>()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{ByteCounter, Counter, IBytesCounter, ICounterExt, IWordsCounter};
+
+    #[test]
+    fn counter_ext_test() {
+        let result = "the quick the lazy the quick".count_words();
+        assert_eq!(result.most()[0].word, "the");
+        assert_eq!(result.least()[0].word, "lazy");
+        assert_eq!(result.find_by_key("quick").unwrap().count, 2);
+        assert!(result.find_by_key("dog").is_none());
+        assert_eq!(result.find_by_num(2)[0].key(), "quick");
+
+        let result = b"aabbc".count_bytes();
+        assert_eq!(result.most().len(), 2);
+        assert_eq!(
+            result.least(),
+            vec![ByteCounter {
+                byte: b'c',
+                count: 1
+            }]
+        );
+        assert_eq!(result.find_by_key(&b'a').unwrap().count(), 2);
+        assert_eq!(result.counter_filter(|x| x.byte > b'a').len(), 2);
+
+        let empty = "".count_words();
+        assert!(empty.most().is_empty());
+        assert!(empty.least().is_empty());
+    }
+}
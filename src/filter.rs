@@ -0,0 +1,104 @@
+use crate::{CharsCounter, ICharsCounter};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+type CharFilter<'a> = Box<dyn Fn(&char) -> bool + 'a>;
+
+/// Combines several predicates that a character must all satisfy to be counted.
+///
+/// ```
+/// use chars_counter::{CharFilterBuilder, ICharsCounter};
+///
+/// let result = CharFilterBuilder::new()
+///     .ascii()
+///     .alphabetic()
+///     .custom(|x| !"aeiou".contains(*x))
+///     .count("Héllo wörld");
+/// assert_eq!(result, "Hllwrld".count_chars());
+/// ```
+#[derive(Default)]
+pub struct CharFilterBuilder<'a> {
+    predicates: Vec<CharFilter<'a>>,
+}
+
+impl<'a> CharFilterBuilder<'a> {
+    /// A builder without predicates, which counts every character.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ascii(self) -> Self {
+        self.custom(|x| x.is_ascii())
+    }
+
+    pub fn alphabetic(self) -> Self {
+        self.custom(|x| x.is_alphabetic())
+    }
+
+    pub fn numeric(self) -> Self {
+        self.custom(|x| x.is_numeric())
+    }
+
+    pub fn not_whitespace(self) -> Self {
+        self.custom(|x| !x.is_whitespace())
+    }
+
+    pub fn custom<P>(mut self, predicate: P) -> Self
+    where
+        P: Fn(&char) -> bool + 'a,
+    {
+        self.predicates.push(Box::new(predicate));
+        self
+    }
+
+    /// Whether `c` satisfies every predicate added so far.
+    pub fn matches(&self, c: &char) -> bool {
+        self.predicates.iter().all(|x| x(c))
+    }
+
+    pub fn count<T: ICharsCounter + ?Sized>(&self, s: &T) -> Vec<CharsCounter> {
+        s.count_chars_filter(|x| self.matches(x))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{CharFilterBuilder, ICharsCounter};
+
+    #[test]
+    fn two_predicates_test() {
+        let str = "Hello world! Grüße 123";
+        let result = CharFilterBuilder::new().ascii().alphabetic().count(str);
+        assert_eq!(
+            result,
+            str.count_chars_filter(|x| x.is_ascii() && x.is_alphabetic())
+        );
+    }
+
+    #[test]
+    fn three_predicates_test() {
+        let str = "Hello world! Grüße 123";
+        let result = CharFilterBuilder::new()
+            .not_whitespace()
+            .ascii()
+            .custom(|x| !"aeiouAEIOU".contains(*x))
+            .count(str);
+        assert_eq!(
+            result,
+            str.count_chars_filter(|x| !x.is_whitespace()
+                && x.is_ascii()
+                && !"aeiouAEIOU".contains(*x))
+        );
+    }
+
+    #[test]
+    fn empty_builder_test() {
+        let str = "Hello world!";
+        assert_eq!(CharFilterBuilder::new().count(str), str.count_chars());
+        let chars = str.chars().collect::<Vec<_>>();
+        assert_eq!(
+            CharFilterBuilder::new().numeric().count(&chars[..]),
+            str.count_chars_numeric()
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use crate::counter::{cmp_counters, into_sorted};
+use crate::Map;
+use crate::{Counter, ICounterExt};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The count of one extended grapheme cluster.
+///
+/// Unlike `CharsCounter`, the key is a `String`, since a user-perceived character
+/// can be made of several scalar values.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GraphemeCounter {
+    pub grapheme: String,
+    pub count: usize,
+}
+
+impl Counter for GraphemeCounter {
+    type Key = str;
+
+    fn key(&self) -> &str {
+        &self.grapheme
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl Ord for GraphemeCounter {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_counters(self, other)
+    }
+}
+
+impl PartialOrd for GraphemeCounter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub trait IGraphemesCounter {
+    fn count_graphemes(&self) -> Vec<GraphemeCounter>;
+}
+
+/// Queries on the result of `count_graphemes`, with `find_by_grapheme` looking a cluster up
+/// by its text.
+pub trait IGraphemeCounterExt {
+    fn most_graphemes(&self) -> Vec<GraphemeCounter>;
+    fn least_graphemes(&self) -> Vec<GraphemeCounter>;
+    fn find_by_grapheme(&self, g: &str) -> Option<GraphemeCounter>;
+}
+
+impl IGraphemesCounter for str {
+    fn count_graphemes(&self) -> Vec<GraphemeCounter> {
+        let mut counts = Map::<&str, usize>::new();
+        for g in self.graphemes(true) {
+            *counts.entry(g).or_insert(0) += 1;
+        }
+        into_sorted(counts, |grapheme, count| GraphemeCounter {
+            grapheme: grapheme.to_string(),
+            count,
+        })
+    }
+}
+
+impl IGraphemeCounterExt for Vec<GraphemeCounter> {
+    fn most_graphemes(&self) -> Vec<GraphemeCounter> {
+        self.most()
+    }
+
+    fn least_graphemes(&self) -> Vec<GraphemeCounter> {
+        self.least()
+    }
+
+    fn find_by_grapheme(&self, g: &str) -> Option<GraphemeCounter> {
+        self.find_by_key(g)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{
+        GraphemeCounter, ICharsCounter, ICounterExt, IGraphemeCounterExt, IGraphemesCounter,
+    };
+
+    #[test]
+    fn count_graphemes_test() {
+        let str = "e\u{301}e\u{301}\u{e9}";
+        assert_eq!(str.count_chars().len(), 3);
+        let result = str.count_graphemes();
+        assert_eq!(
+            result,
+            vec![
+                GraphemeCounter {
+                    grapheme: "e\u{301}".to_string(),
+                    count: 2
+                },
+                GraphemeCounter {
+                    grapheme: "\u{e9}".to_string(),
+                    count: 1
+                }
+            ]
+        );
+
+        let str = "\u{1F1EF}\u{1F1F5}!";
+        let result = str.count_graphemes();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[1].grapheme, "\u{1F1EF}\u{1F1F5}");
+    }
+
+    #[test]
+    fn grapheme_ext_test() {
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let flag = "\u{1F1EF}\u{1F1F5}";
+        let str = format!("{family}{family}{flag}{flag}a\u{308}");
+        let result = str.count_graphemes();
+        assert_eq!(result.len(), 3);
+        assert_eq!(
+            result.most_graphemes(),
+            vec![
+                GraphemeCounter {
+                    grapheme: flag.to_string(),
+                    count: 2
+                },
+                GraphemeCounter {
+                    grapheme: family.to_string(),
+                    count: 2
+                }
+            ]
+        );
+        assert_eq!(result.least_graphemes()[0].grapheme, "a\u{308}");
+        assert_eq!(result.find_by_num(2).len(), 2);
+        assert_eq!(result.find_by_grapheme(flag).unwrap().count, 2);
+        assert!(result.find_by_grapheme("a").is_none());
+        assert!(Vec::<GraphemeCounter>::new().most_graphemes().is_empty());
+    }
+}
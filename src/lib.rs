@@ -11,6 +11,8 @@
 //! // You can also use like this:
 //! let result = str.count_chars_numeric();
 //! let result = str.count_chars_alphabetic();
+//! let result = str.count_chars_uppercase();
+//! let result = str.count_chars_lowercase();
 //! let result = str.count_chars_chinese();
 //! // ...... Others you can try by yourself.
 //! // if those can't meet your needs, you can custom your own rules by
@@ -28,53 +30,236 @@
 //! let result = str.count_chars().least_chars().find_by_char('H');
 //! // result = Some(CharsCounter { character: 'H', count: 1 })
 //! ```
+//!
+//! The counters work on anything that derefs to `str`, for example `String`:
+//! ```
+//! use chars_counter::ICharsCounter;
+//!
+//! let string = String::from("Hello world!");
+//! assert_eq!(string.count_chars(), "Hello world!".count_chars());
+//! let borrowed: &String = &string;
+//! assert_eq!(borrowed.count_chars(), "Hello world!".count_chars());
+//! ```
+//!
+//! # Features
+//! - `async`: adds `count_chars_from_stream` for counting the chunks of a `futures_core::Stream`.
+//! - `serde`: derives `Serialize` and `Deserialize` for the counter types.
+//! - `casefold`: adds `count_chars_casefold` for counting after full Unicode case folding.
+//! - `rayon`: adds `count_chars_parallel` and `count_chars_filter_parallel` for counting large inputs on several threads.
+//! - `normalization`: adds `count_chars_normalized` for counting after NFC, NFD, NFKC or NFKD,
+//!   and `count_chars_strip_accents`.
+//! - `graphemes`: adds `IGraphemesCounter::count_graphemes` for counting extended grapheme clusters.
+//! - `words`: adds `IWordsCounter::count_words_unicode` for counting words split on Unicode word boundaries.
+//! - `std` (default): turn it off for `no_std` targets with `alloc`.
+//!   Counting then groups in a `BTreeMap`, and the `OsStr` impl, the reader functions and
+//!   `SharedCharsCounter` are unavailable. `casefold`, `normalization` and `rayon` require
+//!   `std`. The traits keep the same methods, with one deliberate exception: `to_map`
+//!   returns a `BTreeMap` instead of a `HashMap`, as `HashMap` needs `std`.
+//!   `entropy` then takes its logarithm from `libm`, as `f64::log2` needs `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod accumulator;
+mod bytes;
+mod counter;
+mod filter;
+#[cfg(feature = "graphemes")]
+mod graphemes;
+mod ngrams;
+#[cfg(feature = "std")]
+mod reader;
+mod script;
+mod set;
+#[cfg(feature = "std")]
+mod shared;
+#[cfg(feature = "async")]
+mod stream;
+mod tables;
+mod words;
 
-use itertools::Itertools;
+use alloc::collections::BinaryHeap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "casefold")]
+use caseless::Caseless;
+use core::char::DecodeUtf16Error;
+use core::cmp::{Ordering, Reverse};
+use core::fmt;
+use core::ops::RangeInclusive;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "std")]
+use std::ffi::OsStr;
+#[cfg(feature = "normalization")]
+use unicode_normalization::UnicodeNormalization;
+
+pub use accumulator::CharsAccumulator;
+pub use bytes::{ByteCounter, IByteCounterExt, IBytesCounter};
+pub use counter::{Counter, ICounterExt};
+pub use filter::CharFilterBuilder;
+pub use ngrams::{INgramCounterExt, INgramsCounter, NgramCounter};
+#[cfg(feature = "std")]
+pub use reader::{
+    count_chars_by_lines, count_chars_reader, count_chars_reader_filter, count_chars_reader_lossy,
+    LineCounts,
+};
+pub use script::Script;
+pub use set::ICharSet;
+#[cfg(feature = "std")]
+pub use shared::SharedCharsCounter;
+#[cfg(feature = "async")]
+pub use stream::{count_chars_from_stream, count_chars_from_stream_filter};
+pub use words::{IWordCounterExt, IWordsCounter, WordCounter};
+
+#[cfg(feature = "graphemes")]
+pub use graphemes::{GraphemeCounter, IGraphemeCounterExt, IGraphemesCounter};
+
+/// The map characters are grouped in.
+#[cfg(feature = "std")]
+pub(crate) type Map<K, V> = std::collections::HashMap<K, V>;
+#[cfg(not(feature = "std"))]
+pub(crate) type Map<K, V> = alloc::collections::BTreeMap<K, V>;
+#[cfg(feature = "std")]
+pub(crate) type Set<T> = std::collections::HashSet<T>;
+#[cfg(not(feature = "std"))]
+pub(crate) type Set<T> = alloc::collections::BTreeSet<T>;
+
+/// The Unicode normalization form applied by `count_chars_normalized`.
+#[cfg(feature = "normalization")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NormalizationForm {
+    Nfc,
+    Nfd,
+    Nfkc,
+    Nfkd,
+}
+
+/// The smallest chunk, in bytes of a `str` or elements of a `[char]`, handed to a
+/// thread by `count_chars_parallel`.
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_MIN: usize = 64 * 1024;
+
+/// The order of the counters returned by `count_chars_sorted`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SortOrder {
+    /// Descending count, then ascending character, the order of `count_chars`.
+    CountDesc,
+    /// Ascending count, then ascending character.
+    CountAsc,
+    CharAsc,
+    CharDesc,
+}
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CharsCounter {
     pub character: char,
     pub count: usize,
 }
 
-pub trait ICharsCounter {
-    fn count_chars(&self) -> Vec<CharsCounter>;
-    fn count_chars_ascii(&self) -> Vec<CharsCounter>;
-    fn count_chars_numeric(&self) -> Vec<CharsCounter>;
-    fn count_chars_alphabetic(&self) -> Vec<CharsCounter>;
-    fn count_chars_alphanumeric(&self) -> Vec<CharsCounter>;
-    fn count_chars_whitespace(&self) -> Vec<CharsCounter>;
-    fn count_chars_no_whitespace(&self) -> Vec<CharsCounter>;
-    fn count_chars_chinese(&self) -> Vec<CharsCounter>;
+/// Orders by descending count, then ascending character, the order `count_chars` returns.
+impl Ord for CharsCounter {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .count
+            .cmp(&self.count)
+            .then(self.character.cmp(&other.character))
+    }
+}
 
-    fn count_chars_filter<P>(&self, predicate: P) -> Vec<CharsCounter>
-    where
-        P: FnMut(&char) -> bool;
+impl PartialOrd for CharsCounter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
-pub trait ICharCounterExt {
-    fn most_chars(&self) -> Vec<CharsCounter>;
-    fn least_chars(&self) -> Vec<CharsCounter>;
-    fn find_by_num(&self, n: usize) -> Vec<CharsCounter>;
-    fn find_by_char(&self, c: char) -> Option<CharsCounter>;
-    fn counter_filter<P>(&self, predicate: P) -> Vec<CharsCounter>
-    where
-        P: FnMut(&&CharsCounter) -> bool;
+impl fmt::Display for CharsCounter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self.character, self.count)
+    }
 }
 
-impl ICharsCounter for &str {
+pub trait ICharsCounter {
+    /// The characters to count. This is the only method an implementation has to provide.
+    fn chars_iter(&self) -> impl Iterator<Item = char> + '_;
+
     fn count_chars(&self) -> Vec<CharsCounter> {
         self.count_chars_filter(|_| true)
     }
 
+    /// Yields the counters in `count_chars` order. The counts are still buffered, as the
+    /// whole text is counted up front, but the ordering is done lazily with a heap, so
+    /// taking the first few counters of a large alphabet skips sorting the rest.
+    fn count_chars_iter(&self) -> impl Iterator<Item = CharsCounter> {
+        let mut heap = count_map(self.chars_iter())
+            .into_iter()
+            .map(|x| {
+                Reverse(CharsCounter {
+                    character: x.0,
+                    count: x.1,
+                })
+            })
+            .collect::<BinaryHeap<_>>();
+        core::iter::from_fn(move || heap.pop().map(|x| x.0))
+    }
+
+    fn count_chars_sorted(&self, order: SortOrder) -> Vec<CharsCounter> {
+        let mut result = self.count_chars();
+        match order {
+            SortOrder::CountDesc => {}
+            SortOrder::CountAsc => {
+                result.sort_by(|x, y| x.count.cmp(&y.count).then(x.character.cmp(&y.character)))
+            }
+            SortOrder::CharAsc => result.sort_by_key(|x| x.character),
+            SortOrder::CharDesc => result.sort_by_key(|x| Reverse(x.character)),
+        }
+        result
+    }
+
     fn count_chars_ascii(&self) -> Vec<CharsCounter> {
         self.count_chars_filter(|x| x.is_ascii())
     }
 
+    /// Same result as `count_chars_ascii`, tallied into a fixed array instead of a map.
+    fn count_chars_ascii_fast(&self) -> Vec<CharsCounter> {
+        let mut counts = [0usize; 128];
+        for c in self.chars_iter().filter(|x| x.is_ascii()) {
+            counts[c as usize] += 1;
+        }
+        ascii_counters(&counts)
+    }
+
     fn count_chars_numeric(&self) -> Vec<CharsCounter> {
         self.count_chars_filter(|x| x.is_numeric())
     }
 
+    /// Counts only '0'..='9', unlike `count_chars_numeric` which also matches '½' or '٣'.
+    fn count_chars_ascii_digit(&self) -> Vec<CharsCounter> {
+        self.count_chars_filter(|x| x.is_ascii_digit())
+    }
+
+    /// Counts only '0'..='9', 'a'..='f' and 'A'..='F'.
+    fn count_chars_ascii_hexdigit(&self) -> Vec<CharsCounter> {
+        self.count_chars_filter(|x| x.is_ascii_hexdigit())
+    }
+
+    /// Counts the digits valid in `radix` as accepted by `char::is_digit`,
+    /// e.g. '0'..='9', 'a'..='f' and 'A'..='F' for 16.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is outside `2..=36`, like `char::is_digit`.
+    fn count_chars_digit_radix(&self, radix: u32) -> Vec<CharsCounter> {
+        assert!(
+            (2..=36).contains(&radix),
+            "radix must be in 2..=36, got {radix}"
+        );
+        self.count_chars_filter(|x| x.is_digit(radix))
+    }
+
     fn count_chars_alphabetic(&self) -> Vec<CharsCounter> {
         self.count_chars_filter(|x| x.is_alphabetic())
     }
@@ -83,91 +268,879 @@ impl ICharsCounter for &str {
         self.count_chars_filter(|x| x.is_alphanumeric())
     }
 
+    fn count_chars_uppercase(&self) -> Vec<CharsCounter> {
+        self.count_chars_filter(|x| x.is_uppercase())
+    }
+
+    fn count_chars_lowercase(&self) -> Vec<CharsCounter> {
+        self.count_chars_filter(|x| x.is_lowercase())
+    }
+
     fn count_chars_whitespace(&self) -> Vec<CharsCounter> {
         self.count_chars_filter(|x| x.is_whitespace())
     }
 
+    /// Counts the ASCII vowels 'a', 'e', 'i', 'o' and 'u' in either case; 'y' is not included.
+    fn count_chars_vowels(&self) -> Vec<CharsCounter> {
+        self.count_chars_filter(|x| matches!(x.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u'))
+    }
+
+    /// Same as `count_chars_vowels`, but also counts 'y' and 'Y'.
+    fn count_chars_vowels_with_y(&self) -> Vec<CharsCounter> {
+        self.count_chars_filter(|x| {
+            matches!(x.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u' | 'y')
+        })
+    }
+
+    /// Counts alphabetic characters that are not matched by `count_chars_vowels`, so 'y'
+    /// is a consonant. Any other letter counts too, including 'é' and non-Latin letters.
+    fn count_chars_consonants(&self) -> Vec<CharsCounter> {
+        self.count_chars_filter(|x| {
+            x.is_alphabetic() && !matches!(x.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u')
+        })
+    }
+
+    /// Skips all Unicode whitespace, including '\t', '\n' and U+00A0 NO-BREAK SPACE.
     fn count_chars_no_whitespace(&self) -> Vec<CharsCounter> {
+        self.count_chars_filter(|x| !x.is_whitespace())
+    }
+
+    /// Skips only ' ', unlike `count_chars_no_whitespace`.
+    fn count_chars_no_space(&self) -> Vec<CharsCounter> {
         self.count_chars_filter(|x| *x != ' ')
     }
 
+    fn count_chars_control(&self) -> Vec<CharsCounter> {
+        self.count_chars_filter(|x| x.is_control())
+    }
+
+    /// Counts the CJK ideographs of `Script::Han`, including the extension blocks.
+    /// CJK punctuation such as '，' and '。' is deliberately excluded.
     fn count_chars_chinese(&self) -> Vec<CharsCounter> {
-        self.count_chars_filter(|x| *x as u32 >= 19968 && *x as u32 <= 40959)
+        self.count_chars_in_script(Script::Han)
+    }
+
+    /// Counts Hiragana (U+3040–U+309F).
+    fn count_chars_hiragana(&self) -> Vec<CharsCounter> {
+        self.count_chars_in_script(Script::Hiragana)
+    }
+
+    /// Counts Katakana (U+30A0–U+30FF) and halfwidth Katakana (U+FF65–U+FF9F).
+    fn count_chars_katakana(&self) -> Vec<CharsCounter> {
+        self.count_chars_in_script(Script::Katakana)
+    }
+
+    /// Counts Hiragana, Katakana and the ideographs matched by `count_chars_chinese`.
+    fn count_chars_japanese(&self) -> Vec<CharsCounter> {
+        self.count_chars_filter(|x| {
+            Script::Hiragana.contains(*x)
+                || Script::Katakana.contains(*x)
+                || Script::Han.contains(*x)
+        })
+    }
+
+    /// Counts Hangul syllables (U+AC00–U+D7A3), Jamo (U+1100–U+11FF) and
+    /// compatibility Jamo (U+3130–U+318F).
+    fn count_chars_hangul(&self) -> Vec<CharsCounter> {
+        self.count_chars_in_script(Script::Hangul)
+    }
+
+    /// Counts Cyrillic (U+0400–U+04FF), Cyrillic Supplement (U+0500–U+052F) and
+    /// Cyrillic Extended-A/B/C (U+2DE0–U+2DFF, U+A640–U+A69F, U+1C80–U+1C8F).
+    fn count_chars_cyrillic(&self) -> Vec<CharsCounter> {
+        self.count_chars_in_script(Script::Cyrillic)
+    }
+
+    /// Counts Greek and Coptic (U+0370–U+03FF) and Greek Extended (U+1F00–U+1FFF).
+    fn count_chars_greek(&self) -> Vec<CharsCounter> {
+        self.count_chars_in_script(Script::Greek)
+    }
+
+    /// Counts Arabic (U+0600–U+06FF), Arabic Supplement (U+0750–U+077F) and the
+    /// Presentation Forms-A/B blocks (U+FB50–U+FDFF, U+FE70–U+FEFF).
+    ///
+    /// Whole blocks are matched, so the Arabic-Indic digits '٠'..='٩' and Arabic
+    /// punctuation such as '،' are included.
+    fn count_chars_arabic(&self) -> Vec<CharsCounter> {
+        self.count_chars_in_script(Script::Arabic)
+    }
+
+    fn count_chars_in_script(&self, script: Script) -> Vec<CharsCounter> {
+        self.count_chars_filter(|x| script.contains(*x))
+    }
+
+    /// Counts only the characters that appear in `set`, a `str`, `[char]`, `HashSet<char>`
+    /// or `BTreeSet<char>`; duplicates in a `str` or `[char]` are ignored.
+    fn count_chars_in_set<S: ICharSet + ?Sized>(&self, set: &S) -> Vec<CharsCounter> {
+        let contains = set.lookup();
+        self.count_chars_filter(|x| contains(*x))
+    }
+
+    /// Counts every character except those in `set`; an empty `set` counts everything.
+    fn count_chars_except<S: ICharSet + ?Sized>(&self, set: &S) -> Vec<CharsCounter> {
+        let contains = set.lookup();
+        self.count_chars_filter(|x| !contains(*x))
+    }
+
+    /// Counts characters inside `range`. An inverted range such as `'z'..='a'` is empty
+    /// and counts nothing.
+    fn count_chars_in_range(&self, range: RangeInclusive<char>) -> Vec<CharsCounter> {
+        self.count_chars_filter(|x| range.contains(x))
+    }
+
+    /// Counts characters falling in any of `ranges`, so disjoint blocks can be combined.
+    fn count_chars_in_ranges(&self, ranges: &[RangeInclusive<char>]) -> Vec<CharsCounter> {
+        self.count_chars_filter(|x| ranges.iter().any(|range| range.contains(x)))
+    }
+
+    /// Counts the ASCII punctuation set (which also includes symbols like '$' and '+')
+    /// plus the Unicode punctuation categories Pc, Pd, Ps, Pe, Pi, Pf and Po.
+    /// Symbols outside ASCII, such as '€' or '©', are not counted.
+    fn count_chars_punctuation(&self) -> Vec<CharsCounter> {
+        self.count_chars_filter(|x| {
+            x.is_ascii_punctuation() || tables::in_table(tables::PUNCTUATION, *x)
+        })
+    }
+
+    /// Counts scalar values from the main emoji blocks and the regional indicators:
+    /// Miscellaneous Symbols (U+2600–U+26FF), Dingbats (U+2700–U+27BF), Regional
+    /// Indicators (U+1F1E6–U+1F1FF), Miscellaneous Symbols and Pictographs
+    /// (U+1F300–U+1F5FF), Emoticons (U+1F600–U+1F64F), Transport and Map Symbols
+    /// (U+1F680–U+1F6FF), Supplemental Symbols and Pictographs (U+1F900–U+1F9FF) and
+    /// Symbols and Pictographs Extended-A (U+1FA70–U+1FAFF).
+    ///
+    /// Sequences are not merged: a ZWJ family or a flag is counted as its component
+    /// code points, and skin tone modifiers are counted on their own. Variation
+    /// selectors, the zero width joiner and ASCII keycap bases like '#' are skipped.
+    fn count_chars_emoji(&self) -> Vec<CharsCounter> {
+        self.count_chars_filter(|x| tables::in_table(tables::EMOJI, *x))
+    }
+
+    /// Counts characters case-insensitively, folding each one with `char::to_lowercase`.
+    ///
+    /// The lowercase form is the key of the merged bucket. When the lowercase mapping
+    /// expands to several characters, only the first one is kept, e.g. 'İ' becomes 'i'.
+    fn count_chars_ignore_case(&self) -> Vec<CharsCounter> {
+        self.count_chars_by_key(|x| x.to_lowercase().next().unwrap_or(x))
+    }
+
+    /// Counts characters case-insensitively like `count_chars_ignore_case`, but keyed on
+    /// the first character of `char::to_uppercase`, e.g. 'ß' (uppercase "SS") becomes 'S'.
+    fn count_chars_fold_upper(&self) -> Vec<CharsCounter> {
+        self.count_chars_by_key(|x| x.to_uppercase().next().unwrap_or(x))
+    }
+
+    /// Counts characters after full Unicode case folding, merging every case variant
+    /// under its folded form, e.g. 'ẞ' and 'ß' under 's' and 'Σ', 'σ' and 'ς' under 'σ'.
+    ///
+    /// When folding expands to several characters, each of them is counted, so 'ß'
+    /// adds two to 's' and 'İ' adds one to 'i' and one to U+0307. The default (non
+    /// Turkic) folding is used, so 'I' folds to 'i' and 'ı' is left alone.
+    #[cfg(feature = "casefold")]
+    fn count_chars_casefold(&self) -> Vec<CharsCounter> {
+        count_chars_from_iter(self.chars_iter().default_case_fold())
+    }
+
+    /// Counts chunks of the text on the rayon thread pool and merges them, giving the
+    /// same result as `count_chars`, ties included. Chunks are split on char boundaries.
+    #[cfg(feature = "rayon")]
+    fn count_chars_parallel(&self) -> Vec<CharsCounter> {
+        self.count_chars_filter_parallel(|_| true)
+    }
+
+    /// Same as `count_chars_parallel`, counting only the characters accepted by `predicate`.
+    /// The default collects `chars_iter` into a `Vec<char>` and counts that in parallel.
+    #[cfg(feature = "rayon")]
+    fn count_chars_filter_parallel<P>(&self, predicate: P) -> Vec<CharsCounter>
+    where
+        P: Fn(&char) -> bool + Sync,
+    {
+        self.chars_iter()
+            .collect::<Vec<_>>()
+            .count_chars_filter_parallel(predicate)
+    }
+
+    /// Normalizes the text to `form` before counting, so that e.g. with
+    /// `NormalizationForm::Nfc` both "e\u{301}" and "\u{e9}" are counted as 'é'.
+    #[cfg(feature = "normalization")]
+    fn count_chars_normalized(&self, form: NormalizationForm) -> Vec<CharsCounter> {
+        match form {
+            NormalizationForm::Nfc => count_chars_from_iter(self.chars_iter().nfc()),
+            NormalizationForm::Nfd => count_chars_from_iter(self.chars_iter().nfd()),
+            NormalizationForm::Nfkc => count_chars_from_iter(self.chars_iter().nfkc()),
+            NormalizationForm::Nfkd => count_chars_from_iter(self.chars_iter().nfkd()),
+        }
+    }
+
+    /// Decomposes the text with NFD and drops the nonspacing marks (category Mn), so
+    /// 'é', 'ê' and 'e' are all counted as 'e'. Only marks are stripped, there is no
+    /// transliteration: characters without a decomposition, like 'ø' or 'ß', are
+    /// counted unchanged.
+    #[cfg(feature = "normalization")]
+    fn count_chars_strip_accents(&self) -> Vec<CharsCounter> {
+        count_chars_from_iter(
+            self.chars_iter()
+                .nfd()
+                .filter(|x| !tables::in_table(tables::NONSPACING_MARK, *x)),
+        )
     }
 
     fn count_chars_filter<P>(&self, predicate: P) -> Vec<CharsCounter>
     where
         P: FnMut(&char) -> bool,
     {
+        count_chars_from_iter(self.chars_iter().filter(predicate))
+    }
+
+    /// Counts each line separately, one vector per line as returned by `str::lines`,
+    /// so "\r\n" endings are stripped and empty lines yield empty vectors.
+    ///
+    /// ```
+    /// use chars_counter::ICharsCounter;
+    ///
+    /// let result = "aab\r\n\nc".count_chars_per_line();
+    /// assert_eq!(result.len(), 3);
+    /// assert_eq!(result[0], "aab".count_chars());
+    /// assert!(result[1].is_empty());
+    /// assert_eq!(result[2], "c".count_chars());
+    /// ```
+    fn count_chars_per_line(&self) -> Vec<Vec<CharsCounter>> {
+        self.count_chars_lines_filter(|_| true)
+    }
+
+    /// Same as `count_chars_per_line`, counting only the characters accepted by `predicate`.
+    fn count_chars_lines_filter<P>(&self, mut predicate: P) -> Vec<Vec<CharsCounter>>
+    where
+        P: FnMut(&char) -> bool,
+    {
+        let mut lines = Vec::new();
+        let mut counts = Map::<char, usize>::new();
+        let mut line_open = false;
+        let mut carriage_return = false;
+        for c in self.chars_iter() {
+            if carriage_return && c != '\n' && predicate(&'\r') {
+                *counts.entry('\r').or_insert(0) += 1;
+            }
+            carriage_return = c == '\r';
+            if c == '\n' {
+                lines.push(into_counters(core::mem::take(&mut counts)));
+                line_open = false;
+                continue;
+            }
+            line_open = true;
+            if c != '\r' && predicate(&c) {
+                *counts.entry(c).or_insert(0) += 1;
+            }
+        }
+        if carriage_return && predicate(&'\r') {
+            *counts.entry('\r').or_insert(0) += 1;
+        }
+        if line_open {
+            lines.push(into_counters(counts));
+        }
+        lines
+    }
+
+    /// Maps every character through `key` and counts the keys, so characters sharing a
+    /// key are summed into one counter whose `character` is the key.
+    fn count_chars_by_key<F>(&self, key: F) -> Vec<CharsCounter>
+    where
+        F: FnMut(char) -> char,
+    {
+        count_chars_from_iter(self.chars_iter().map(key))
+    }
+
+    /// Keeps the characters accepted by `predicate`, then counts them by `key`.
+    fn count_chars_filter_key<P, F>(&self, predicate: P, key: F) -> Vec<CharsCounter>
+    where
+        P: FnMut(&char) -> bool,
+        F: FnMut(char) -> char,
+    {
+        count_chars_from_iter(self.chars_iter().filter(predicate).map(key))
+    }
+}
+
+/// Counting for any iterator of characters, consuming it.
+pub trait IIterCharsCounter {
+    fn count_chars(self) -> Vec<CharsCounter>;
+    fn count_chars_filter<P>(self, predicate: P) -> Vec<CharsCounter>
+    where
+        P: FnMut(&char) -> bool;
+}
+
+pub trait ICharCounterExt {
+    fn most_chars(&self) -> Vec<CharsCounter>;
+    fn least_chars(&self) -> Vec<CharsCounter>;
+    /// Returns the characters with the `n`-th largest distinct count, counting from 0,
+    /// so `nth_most(0)` equals `most_chars`. Empty when there are fewer distinct counts.
+    fn nth_most(&self, n: usize) -> Vec<CharsCounter>;
+    /// The first counter, or `None` when empty. Ties go to the smallest character,
+    /// following the `count_chars` order.
+    fn top_char(&self) -> Option<CharsCounter>;
+    /// The last counter, or `None` when empty. Ties go to the largest character.
+    fn bottom_char(&self) -> Option<CharsCounter>;
+    /// Returns the first `n` counters. The cut is strict: characters tied with the last
+    /// one kept are dropped, in character order, once `n` is reached.
+    fn top_n(&self, n: usize) -> Vec<CharsCounter>;
+    /// Returns the last `n` counters reversed, so the rarest character comes first.
+    /// The cut is strict like `top_n`.
+    fn bottom_n(&self, n: usize) -> Vec<CharsCounter>;
+    fn find_by_num(&self, n: usize) -> Vec<CharsCounter>;
+    /// Keeps the counters whose count lies in `min..=max`, empty when `min > max`.
+    fn find_by_num_range(&self, min: usize, max: usize) -> Vec<CharsCounter>;
+    /// Keeps the counters with a count of `n` or more.
+    fn at_least(&self, n: usize) -> Vec<CharsCounter>;
+    /// Keeps the counters with a count of `n` or less.
+    fn at_most(&self, n: usize) -> Vec<CharsCounter>;
+    fn find_by_char(&self, c: char) -> Option<CharsCounter>;
+    fn contains_char(&self, c: char) -> bool;
+    fn count_of(&self, c: char) -> usize;
+    /// The exact number of characters counted, `0` for an empty vector.
+    ///
+    /// ```
+    /// use chars_counter::{ICharCounterExt, ICharsCounter};
+    ///
+    /// let str = "Hello world!";
+    /// let letters = str.count_chars_alphabetic().total_count();
+    /// let share = letters as f64 / str.count_chars().total_count() as f64;
+    /// assert_eq!(letters, 10);
+    /// assert_eq!(share, 10.0 / 12.0);
+    /// ```
+    fn total_count(&self) -> usize;
+    /// The number of distinct characters counted, `0` for an empty vector.
+    fn distinct_count(&self) -> usize;
+    /// Pairs each counter with its 1-based competition rank: tied counts share a rank
+    /// and the next count skips the tied places, e.g. 1, 1, 3.
+    fn ranked(&self) -> Vec<(usize, CharsCounter)>;
+    /// The 0-based position of `c`, so ties are ranked by character.
+    fn rank_of(&self, c: char) -> Option<usize>;
+    /// The 0-based dense rank of the count of `c` among the distinct counts: tied
+    /// characters share a rank and the next count takes the next rank, e.g. 0, 0, 1.
+    fn rank_by_count_of(&self, c: char) -> Option<usize>;
+    /// A `HashMap`, or a `BTreeMap` without the `std` feature.
+    fn to_map(&self) -> Map<char, usize>;
+    /// Sums the counts of both sides per character and sorts the result again.
+    fn merge(&self, other: &[CharsCounter]) -> Vec<CharsCounter>;
+    /// Subtracts the counts of `other` per character, removing the characters whose
+    /// count drops to zero or below. Characters only present in `other` are ignored.
+    fn subtract(&self, other: &[CharsCounter]) -> Vec<CharsCounter>;
+    /// Pairs each counter with its share of `total_count`, keeping the existing order.
+    /// Empty when `total_count` is 0, so there is no division by zero.
+    fn frequencies(&self) -> Vec<(CharsCounter, f64)>;
+    /// Shannon entropy of the distribution in bits, the sum of `-p * log2(p)` over the
+    /// frequencies: `0.0` for an empty vector or a single character, `log2(n)` for
+    /// `n` characters counted once each.
+    fn entropy(&self) -> f64;
+    /// Renders one line per counter with a bar of '#' scaled so the largest count
+    /// fills `max_width` columns, e.g. `'a' 3 ###`. Only a zero count gets no bar.
+    fn to_histogram(&self, max_width: usize) -> String;
+    /// Renders a `character,count` header and one row per counter as RFC 4180 CSV,
+    /// with CRLF line ends. A comma, quote, CR or LF character is quoted, e.g. `","`.
+    fn to_csv(&self) -> String;
+    /// Renders a JSON array like `[{"character":"l","count":3}]` without needing serde.
+    /// Quotes and backslashes are escaped, control characters as `\u00XX`.
+    fn to_json(&self) -> String;
+    fn counter_filter<P>(&self, predicate: P) -> Vec<CharsCounter>
+    where
+        P: FnMut(&&CharsCounter) -> bool;
+}
+
+impl ICharsCounter for str {
+    fn chars_iter(&self) -> impl Iterator<Item = char> + '_ {
         self.chars()
-            .filter(predicate)
-            .into_group_map_by(|&x| x)
-            .into_iter()
-            .map(|x| CharsCounter {
-                character: x.0,
-                count: x.1.len(),
-            })
-            .sorted_by(|x, y| y.count.cmp(&x.count).then(x.character.cmp(&y.character)))
-            .collect::<Vec<_>>()
+    }
+
+    /// ASCII characters are tallied in a fixed array and only the others are hashed.
+    fn count_chars(&self) -> Vec<CharsCounter> {
+        let mut ascii = [0usize; 128];
+        let mut others = Map::<char, usize>::new();
+        for c in self.chars() {
+            if c.is_ascii() {
+                ascii[c as usize] += 1;
+            } else {
+                *others.entry(c).or_insert(0) += 1;
+            }
+        }
+        mixed_counters(&ascii, others)
+    }
+
+    fn count_chars_ascii_fast(&self) -> Vec<CharsCounter> {
+        let mut counts = [0usize; 128];
+        for b in self.bytes().filter(|x| x.is_ascii()) {
+            counts[b as usize] += 1;
+        }
+        ascii_counters(&counts)
+    }
+
+    #[cfg(feature = "rayon")]
+    fn count_chars_filter_parallel<P>(&self, predicate: P) -> Vec<CharsCounter>
+    where
+        P: Fn(&char) -> bool + Sync,
+    {
+        let chunk_size = (self.len() / rayon::current_num_threads()).max(PARALLEL_CHUNK_MIN);
+        let counts = split_chunks(self, chunk_size)
+            .into_par_iter()
+            .map(|x| count_map(x.chars().filter(&predicate)))
+            .reduce(Map::new, merge_maps);
+        into_counters(counts)
+    }
+}
+
+/// Counts the lossy UTF-8 conversion of the `OsStr`: each invalid sequence is
+/// replaced by U+FFFD and counted as that character.
+///
+/// ```
+/// use chars_counter::ICharsCounter;
+/// use std::path::Path;
+///
+/// let name = Path::new("/tmp/report.txt").file_name().unwrap();
+/// assert_eq!(name.count_chars(), "report.txt".count_chars());
+/// ```
+#[cfg(feature = "std")]
+impl ICharsCounter for OsStr {
+    fn chars_iter(&self) -> impl Iterator<Item = char> + '_ {
+        let valid = self.to_str();
+        let lossy = match valid {
+            Some(_) => Vec::new(),
+            None => self.to_string_lossy().chars().collect::<Vec<_>>(),
+        };
+        valid.into_iter().flat_map(str::chars).chain(lossy)
+    }
+
+    #[cfg(feature = "rayon")]
+    fn count_chars_filter_parallel<P>(&self, predicate: P) -> Vec<CharsCounter>
+    where
+        P: Fn(&char) -> bool + Sync,
+    {
+        self.to_string_lossy()
+            .count_chars_filter_parallel(predicate)
+    }
+}
+
+impl ICharsCounter for [char] {
+    fn chars_iter(&self) -> impl Iterator<Item = char> + '_ {
+        self.iter().copied()
+    }
+
+    #[cfg(feature = "rayon")]
+    fn count_chars_filter_parallel<P>(&self, predicate: P) -> Vec<CharsCounter>
+    where
+        P: Fn(&char) -> bool + Sync,
+    {
+        let chunk_size = (self.len() / rayon::current_num_threads()).max(PARALLEL_CHUNK_MIN);
+        let counts = self
+            .par_chunks(chunk_size)
+            .map(|x| count_map(x.iter().copied().filter(&predicate)))
+            .reduce(Map::new, merge_maps);
+        into_counters(counts)
+    }
+}
+
+impl<I> IIterCharsCounter for I
+where
+    I: Iterator<Item = char>,
+{
+    fn count_chars(self) -> Vec<CharsCounter> {
+        count_chars_from_iter(self)
+    }
+
+    fn count_chars_filter<P>(self, predicate: P) -> Vec<CharsCounter>
+    where
+        P: FnMut(&char) -> bool,
+    {
+        count_chars_from_iter(self.filter(predicate))
+    }
+}
+
+/// Counts the characters yielded by any iterator, sorted like `count_chars`.
+pub fn count_chars_from_iter<I>(chars: I) -> Vec<CharsCounter>
+where
+    I: IntoIterator<Item = char>,
+{
+    into_counters(count_map(chars))
+}
+
+fn count_map<I>(chars: I) -> Map<char, usize>
+where
+    I: IntoIterator<Item = char>,
+{
+    let mut counts = Map::<char, usize>::new();
+    for c in chars {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[cfg(feature = "rayon")]
+fn merge_maps(mut acc: Map<char, usize>, counts: Map<char, usize>) -> Map<char, usize> {
+    for (c, n) in counts {
+        *acc.entry(c).or_insert(0) += n;
+    }
+    acc
+}
+
+fn ascii_counters(counts: &[usize; 128]) -> Vec<CharsCounter> {
+    let mut result = counts
+        .iter()
+        .enumerate()
+        .filter(|x| *x.1 > 0)
+        .map(|x| CharsCounter {
+            character: x.0 as u8 as char,
+            count: *x.1,
+        })
+        .collect::<Vec<_>>();
+    result.sort();
+    result
+}
+
+#[cfg(feature = "std")]
+fn log2(x: f64) -> f64 {
+    x.log2()
+}
+
+#[cfg(not(feature = "std"))]
+fn log2(x: f64) -> f64 {
+    libm::log2(x)
+}
+
+fn mixed_counters(ascii: &[usize; 128], others: Map<char, usize>) -> Vec<CharsCounter> {
+    let mut result = ascii_counters(ascii);
+    if !others.is_empty() {
+        result.extend(into_counters(others));
+        result.sort();
+    }
+    result
+}
+
+fn into_counters(counts: Map<char, usize>) -> Vec<CharsCounter> {
+    let mut result = counts
+        .into_iter()
+        .map(|x| CharsCounter {
+            character: x.0,
+            count: x.1,
+        })
+        .collect::<Vec<_>>();
+    result.sort();
+    result
+}
+
+#[cfg(feature = "rayon")]
+fn split_chunks(s: &str, chunk_size: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = s;
+    while rest.len() > chunk_size {
+        let mut end = chunk_size;
+        while !rest.is_char_boundary(end) {
+            end += 1;
+        }
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    if !rest.is_empty() {
+        chunks.push(rest);
+    }
+    chunks
+}
+
+/// Decodes UTF-16 code units and counts them, replacing unpaired surrogates with U+FFFD.
+pub fn count_chars_utf16(units: &[u16]) -> Vec<CharsCounter> {
+    count_chars_from_iter(
+        char::decode_utf16(units.iter().copied()).map(|x| x.unwrap_or(char::REPLACEMENT_CHARACTER)),
+    )
+}
+
+/// Decodes UTF-16 code units and counts them, failing on the first unpaired surrogate.
+pub fn count_chars_utf16_strict(units: &[u16]) -> Result<Vec<CharsCounter>, DecodeUtf16Error> {
+    let mut error = None;
+    let result = count_chars_from_iter(
+        char::decode_utf16(units.iter().copied())
+            .map_while(|x| x.map_err(|e| error = Some(e)).ok()),
+    );
+    match error {
+        Some(e) => Err(e),
+        None => Ok(result),
     }
 }
 
 impl ICharCounterExt for Vec<CharsCounter> {
     fn most_chars(&self) -> Vec<CharsCounter> {
+        if self.is_empty() {
+            return Vec::new();
+        }
         self.counter_filter(|x| x.count == self[0].count)
     }
 
     fn least_chars(&self) -> Vec<CharsCounter> {
+        if self.is_empty() {
+            return Vec::new();
+        }
         self.counter_filter(|x| x.count == self[self.len() - 1].count)
     }
 
+    fn nth_most(&self, n: usize) -> Vec<CharsCounter> {
+        let mut counts = self.iter().map(|x| x.count).collect::<Vec<_>>();
+        counts.sort_by_key(|x| Reverse(*x));
+        counts.dedup();
+        match counts.get(n) {
+            Some(&count) => self.find_by_num(count),
+            None => Vec::new(),
+        }
+    }
+
+    fn top_char(&self) -> Option<CharsCounter> {
+        self.first().copied()
+    }
+
+    fn bottom_char(&self) -> Option<CharsCounter> {
+        self.last().copied()
+    }
+
+    fn top_n(&self, n: usize) -> Vec<CharsCounter> {
+        self.iter().take(n).copied().collect::<Vec<_>>()
+    }
+
+    fn bottom_n(&self, n: usize) -> Vec<CharsCounter> {
+        self.iter().rev().take(n).copied().collect::<Vec<_>>()
+    }
+
     fn find_by_num(&self, n: usize) -> Vec<CharsCounter> {
         self.counter_filter(|x| x.count == n)
     }
 
+    fn find_by_num_range(&self, min: usize, max: usize) -> Vec<CharsCounter> {
+        self.counter_filter(|x| (min..=max).contains(&x.count))
+    }
+
+    fn at_least(&self, n: usize) -> Vec<CharsCounter> {
+        self.counter_filter(|x| x.count >= n)
+    }
+
+    fn at_most(&self, n: usize) -> Vec<CharsCounter> {
+        self.counter_filter(|x| x.count <= n)
+    }
+
     fn find_by_char(&self, c: char) -> Option<CharsCounter> {
-        self.iter().find(|x| x.character == c).map(|&x| x)
+        self.iter().find(|x| x.character == c).copied()
     }
 
-    fn counter_filter<P>(&self, predicate: P) -> Vec<CharsCounter>
-    where
-        P: FnMut(&&CharsCounter) -> bool,
-    {
-        self.iter()
-            .filter(predicate)
-            .map(|&x| x)
-            .collect::<Vec<_>>()
+    fn contains_char(&self, c: char) -> bool {
+        self.find_by_char(c).is_some()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::{CharsCounter, ICharCounterExt, ICharsCounter};
+    fn count_of(&self, c: char) -> usize {
+        self.find_by_char(c).map(|x| x.count).unwrap_or(0)
+    }
 
-    #[test]
-    fn most_chars_test() {
-        let str = "Hello world!";
-        let result = str.count_chars().most_chars();
-        assert_eq!(
-            result[0],
-            CharsCounter {
-                character: 'l',
-                count: 3
-            }
-        );
+    fn total_count(&self) -> usize {
+        self.iter().map(|x| x.count).sum()
     }
 
-    #[test]
-    fn least_chars_test() {
-        let str = "Hello world!";
-        let result = str.count_chars().least_chars();
-        assert_eq!(
-            result[0],
-            CharsCounter {
-                character: ' ',
-                count: 1
-            }
-        );
+    fn distinct_count(&self) -> usize {
+        self.len()
+    }
+
+    fn ranked(&self) -> Vec<(usize, CharsCounter)> {
+        let mut rank = 0;
+        self.iter()
+            .enumerate()
+            .map(|(i, &x)| {
+                if i == 0 || self[i - 1].count != x.count {
+                    rank = i + 1;
+                }
+                (rank, x)
+            })
+            .collect::<Vec<_>>()
+    }
+
+    fn rank_of(&self, c: char) -> Option<usize> {
+        self.iter().position(|x| x.character == c)
+    }
+
+    fn rank_by_count_of(&self, c: char) -> Option<usize> {
+        let count = self.find_by_char(c)?.count;
+        let mut higher = self
+            .iter()
+            .map(|x| x.count)
+            .filter(|x| *x > count)
+            .collect::<Vec<_>>();
+        higher.sort();
+        higher.dedup();
+        Some(higher.len())
+    }
+
+    fn to_map(&self) -> Map<char, usize> {
+        self.iter().map(|x| (x.character, x.count)).collect()
+    }
+
+    fn merge(&self, other: &[CharsCounter]) -> Vec<CharsCounter> {
+        let mut counts = self.to_map();
+        for x in other {
+            *counts.entry(x.character).or_insert(0) += x.count;
+        }
+        into_counters(counts)
+    }
+
+    fn subtract(&self, other: &[CharsCounter]) -> Vec<CharsCounter> {
+        let other = other
+            .iter()
+            .map(|x| (x.character, x.count))
+            .collect::<Map<_, _>>();
+        let mut result = self
+            .iter()
+            .filter_map(|x| {
+                let count = x
+                    .count
+                    .saturating_sub(other.get(&x.character).copied().unwrap_or(0));
+                (count > 0).then_some(CharsCounter {
+                    character: x.character,
+                    count,
+                })
+            })
+            .collect::<Vec<_>>();
+        result.sort();
+        result
+    }
+
+    fn frequencies(&self) -> Vec<(CharsCounter, f64)> {
+        let total = self.total_count();
+        if total == 0 {
+            return Vec::new();
+        }
+        self.iter()
+            .map(|&x| (x, x.count as f64 / total as f64))
+            .collect::<Vec<_>>()
+    }
+
+    fn entropy(&self) -> f64 {
+        0.0 - self
+            .frequencies()
+            .iter()
+            .filter(|x| x.1 > 0.0)
+            .map(|x| x.1 * log2(x.1))
+            .sum::<f64>()
+    }
+
+    fn to_histogram(&self, max_width: usize) -> String {
+        let max = self.iter().map(|x| x.count).max().unwrap_or(0);
+        let mut histogram = String::new();
+        for x in self {
+            let scaled = x.count as u128 * max_width as u128 + max as u128 / 2;
+            let width = match scaled.checked_div(max as u128) {
+                Some(0) if x.count > 0 && max_width > 0 => 1,
+                Some(width) => width as usize,
+                None => 0,
+            };
+            histogram.push_str(&format!("{:?} {}", x.character, x.count));
+            if width > 0 {
+                histogram.push(' ');
+                histogram.push_str(&"#".repeat(width));
+            }
+            histogram.push('\n');
+        }
+        histogram
+    }
+
+    fn to_csv(&self) -> String {
+        let mut csv = String::from("character,count\r\n");
+        for x in self {
+            match x.character {
+                '"' => csv.push_str("\"\"\"\""),
+                ',' | '\r' | '\n' => {
+                    csv.push('"');
+                    csv.push(x.character);
+                    csv.push('"');
+                }
+                c => csv.push(c),
+            }
+            csv.push_str(&format!(",{}\r\n", x.count));
+        }
+        csv
+    }
+
+    fn to_json(&self) -> String {
+        let mut json = String::from("[");
+        for (i, x) in self.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            json.push_str("{\"character\":\"");
+            match x.character {
+                '"' => json.push_str("\\\""),
+                '\\' => json.push_str("\\\\"),
+                c if c < ' ' => json.push_str(&format!("\\u{:04x}", c as u32)),
+                c => json.push(c),
+            }
+            json.push_str(&format!("\",\"count\":{}}}", x.count));
+        }
+        json.push(']');
+        json
+    }
+
+    fn counter_filter<P>(&self, predicate: P) -> Vec<CharsCounter>
+    where
+        P: FnMut(&&CharsCounter) -> bool,
+    {
+        self.iter().filter(predicate).copied().collect::<Vec<_>>()
+    }
+}
+
+#[cfg(all(test, not(feature = "std")))]
+mod no_std_tests {
+    use crate::{CharsCounter, ICharCounterExt, ICharsCounter};
+    use alloc::vec;
+
+    #[test]
+    fn count_chars_test() {
+        let result = "Hello world!".count_chars();
+        assert_eq!(
+            result.most_chars(),
+            vec![CharsCounter {
+                character: 'l',
+                count: 3
+            }]
+        );
+        assert_eq!(result.least_chars().len(), 7);
+        assert_eq!(result.to_map().get(&'o'), Some(&2));
+        assert_eq!("你好".count_chars_chinese().total_count(), 2);
+        assert_eq!("abcd".count_chars().entropy(), 2.0);
+        assert_eq!("aaaa".count_chars().entropy(), 0.0);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{
+        count_chars_from_iter, count_chars_utf16, count_chars_utf16_strict, CharsCounter,
+        ICharCounterExt, ICharsCounter, IIterCharsCounter, Script, SortOrder,
+    };
+    use std::borrow::Cow;
+    use std::collections::{BTreeSet, HashSet};
+
+    #[test]
+    fn most_chars_test() {
+        let str = "Hello world!";
+        let result = str.count_chars().most_chars();
+        assert_eq!(
+            result[0],
+            CharsCounter {
+                character: 'l',
+                count: 3
+            }
+        );
+    }
+
+    #[test]
+    fn least_chars_test() {
+        let str = "Hello world!";
+        let result = str.count_chars().least_chars();
+        assert_eq!(
+            result[0],
+            CharsCounter {
+                character: ' ',
+                count: 1
+            }
+        );
     }
 
     #[test]
@@ -183,6 +1156,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn find_by_num_range_test() {
+        let str = "Hello world!";
+        let result = str.count_chars().find_by_num_range(2, 3);
+        assert_eq!(
+            result,
+            vec![
+                CharsCounter {
+                    character: 'l',
+                    count: 3
+                },
+                CharsCounter {
+                    character: 'o',
+                    count: 2
+                }
+            ]
+        );
+        assert!(str.count_chars().find_by_num_range(4, 10).is_empty());
+        assert!(str.count_chars().find_by_num_range(3, 2).is_empty());
+    }
+
+    #[test]
+    fn at_least_at_most_test() {
+        let result = "Hello world!".count_chars();
+        assert_eq!(
+            result
+                .at_least(2)
+                .iter()
+                .map(|x| x.character)
+                .collect::<String>(),
+            "lo"
+        );
+        assert_eq!(result.at_least(3).len(), 1);
+        assert!(result.at_least(4).is_empty());
+        assert_eq!(result.at_least(0), result);
+        assert_eq!(
+            result
+                .at_most(2)
+                .iter()
+                .map(|x| x.character)
+                .collect::<String>(),
+            "o !Hderw"
+        );
+        assert_eq!(result.at_most(1), result.find_by_num(1));
+        assert!(result.at_most(0).is_empty());
+    }
+
     #[test]
     fn find_by_char_test() {
         let str = "Hello world!";
@@ -195,4 +1215,1327 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn nth_most_test() {
+        let result = "Hello world!".count_chars();
+        assert_eq!(result.nth_most(0), result.most_chars());
+        assert_eq!(
+            result.nth_most(1),
+            vec![CharsCounter {
+                character: 'o',
+                count: 2
+            }]
+        );
+        assert_eq!(result.nth_most(2), result.find_by_num(1));
+        assert_eq!(result.nth_most(2).len(), 7);
+        assert!(result.nth_most(3).is_empty());
+        assert!("".count_chars().nth_most(0).is_empty());
+    }
+
+    #[test]
+    fn rank_of_test() {
+        let result = "Hello world!".count_chars();
+        assert_eq!(result.rank_of('l'), Some(0));
+        assert_eq!(result.rank_of('o'), Some(1));
+        assert_eq!(result.rank_of(' '), Some(2));
+        assert_eq!(result.rank_of('w'), Some(8));
+        assert_eq!(result.rank_of('z'), None);
+
+        assert_eq!(result.rank_by_count_of('l'), Some(0));
+        assert_eq!(result.rank_by_count_of('o'), Some(1));
+        assert_eq!(result.rank_by_count_of(' '), Some(2));
+        assert_eq!(result.rank_by_count_of('w'), Some(2));
+        assert_eq!(result.rank_by_count_of('z'), None);
+        assert_eq!("".count_chars().rank_of('a'), None);
+    }
+
+    #[test]
+    fn empty_most_least_chars_test() {
+        let str = "";
+        assert_eq!(str.count_chars().most_chars(), vec![]);
+        assert_eq!(str.count_chars().least_chars(), vec![]);
+
+        let str = "abc";
+        assert_eq!(str.count_chars_numeric().most_chars(), vec![]);
+        assert_eq!(str.count_chars_numeric().least_chars(), vec![]);
+
+        let result = "Hello world!".count_chars_filter(|_| false);
+        assert_eq!(result.most_chars(), vec![]);
+        assert_eq!(result.least_chars(), vec![]);
+    }
+
+    #[test]
+    fn no_whitespace_test() {
+        let str = "a\tb\r\nc\u{00A0}d\u{3000}e f";
+        let result = str.count_chars_no_whitespace();
+        assert_eq!(
+            result.iter().map(|x| x.character).collect::<String>(),
+            "abcdef"
+        );
+
+        let result = str.count_chars_no_space();
+        assert!(result.find_by_char(' ').is_none());
+        assert!(result.find_by_char('\t').is_some());
+        assert!(result.find_by_char('\u{00A0}').is_some());
+        assert!(result.find_by_char('\u{3000}').is_some());
+    }
+
+    #[test]
+    fn fold_upper_test() {
+        let result = "aAbB".count_chars_fold_upper();
+        assert_eq!(
+            result,
+            vec![
+                CharsCounter {
+                    character: 'A',
+                    count: 2
+                },
+                CharsCounter {
+                    character: 'B',
+                    count: 2
+                }
+            ]
+        );
+
+        let result = "ßSs1".count_chars_fold_upper();
+        assert_eq!(result.find_by_char('S').unwrap().count, 3);
+        assert_eq!(result.find_by_char('1').unwrap().count, 1);
+    }
+
+    #[test]
+    fn ignore_case_test() {
+        let str = "Hello HELLO";
+        let result = str.count_chars_ignore_case();
+        assert_eq!(
+            result[0],
+            CharsCounter {
+                character: 'l',
+                count: 4
+            }
+        );
+        assert_eq!(result.find_by_char('h').unwrap().count, 2);
+        assert!(result.find_by_char('H').is_none());
+
+        let str = "ÉéΣσİ";
+        let result = str.count_chars_ignore_case();
+        assert_eq!(result.find_by_char('é').unwrap().count, 2);
+        assert_eq!(result.find_by_char('σ').unwrap().count, 2);
+        assert_eq!(result.find_by_char('i').unwrap().count, 1);
+
+        let result = "Hello world!".count_chars_ignore_case();
+        assert_eq!(
+            result.top_n(3).iter().map(|x| x.count).collect::<Vec<_>>(),
+            [3, 2, 1]
+        );
+        assert_eq!(result.find_by_char('h').unwrap().count, 1);
+
+        // Merged buckets are sorted again, by count and then by folded character.
+        let result = "zZyYx".count_chars_ignore_case();
+        assert_eq!(
+            result.iter().map(|x| x.character).collect::<String>(),
+            "yzx"
+        );
+    }
+
+    #[test]
+    fn total_count_test() {
+        let str = "Hello world! 2021";
+        assert_eq!(str.count_chars().total_count(), 17);
+        assert_eq!(str.count_chars_numeric().total_count(), 4);
+        assert_eq!("".count_chars().total_count(), 0);
+    }
+
+    #[test]
+    fn large_input_test() {
+        let str = "Hello world! 你好，世界！".repeat(100_000);
+        let result = str.as_str().count_chars();
+        assert_eq!(result.len(), 15);
+        assert_eq!(
+            result[0],
+            CharsCounter {
+                character: 'l',
+                count: 300_000
+            }
+        );
+        assert_eq!(result.total_count(), 1_900_000);
+    }
+
+    #[test]
+    fn frequencies_test() {
+        let str = "aab";
+        let result = str.count_chars().frequencies();
+        assert_eq!(result[0].0.character, 'a');
+        assert!((result[0].1 - 2.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(result[1].0.character, 'b');
+        assert!((result[1].1 - 1.0 / 3.0).abs() < f64::EPSILON);
+        assert!("".count_chars().frequencies().is_empty());
+
+        let result = "The quick brown fox jumps over the lazy dog 你好".count_chars();
+        let sum = result.frequencies().iter().map(|x| x.1).sum::<f64>();
+        assert!((sum - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn uppercase_lowercase_test() {
+        let str = "Éclair straße ÀB1";
+        let result = str.count_chars_uppercase();
+        assert_eq!(
+            result.iter().map(|x| x.character).collect::<String>(),
+            "BÀÉ"
+        );
+        let result = str.count_chars_lowercase();
+        assert_eq!(result.find_by_char('ß').unwrap().count, 1);
+        assert_eq!(result.find_by_char('a').unwrap().count, 2);
+        assert!(result.find_by_char('É').is_none());
+        assert!(result.find_by_char('1').is_none());
+    }
+
+    #[test]
+    fn punctuation_test() {
+        let included = "!,.?_-—…«»、。（）$+";
+        let excluded = "aZ09é中 \n½€";
+        for c in included.chars() {
+            let str = c.to_string();
+            assert_eq!(
+                str.as_str().count_chars_punctuation().total_count(),
+                1,
+                "{:?}",
+                c
+            );
+        }
+        for c in excluded.chars() {
+            let str = c.to_string();
+            assert!(str.as_str().count_chars_punctuation().is_empty(), "{:?}", c);
+        }
+
+        let str = "Hi, you. Wait, what?! No...";
+        let result = str.count_chars_punctuation();
+        assert_eq!(result.find_by_char(',').unwrap().count, 2);
+        assert_eq!(result.find_by_char('.').unwrap().count, 4);
+        assert_eq!(result.find_by_char('!').unwrap().count, 1);
+        assert_eq!(result.total_count(), 8);
+    }
+
+    #[test]
+    fn owned_str_test() {
+        let str = String::from("Hello world!");
+        assert_eq!(str.count_chars(), "Hello world!".count_chars());
+
+        let str: Cow<'_, str> = Cow::Owned(String::from("Hello world!"));
+        assert_eq!(str.count_chars(), "Hello world!".count_chars());
+        let str: Cow<'_, str> = Cow::Borrowed("Hello world!");
+        assert_eq!(str.count_chars(), "Hello world!".count_chars());
+        assert_eq!(
+            str.count_chars_filter(|x| x.is_alphabetic()),
+            "Hello world!".count_chars_alphabetic()
+        );
+    }
+
+    #[test]
+    fn control_test() {
+        let str = "a\u{0}b\u{7}\u{1b}[0m\u{7f}\n\t\t";
+        let result = str.count_chars_control();
+        assert_eq!(
+            result.most_chars(),
+            vec![CharsCounter {
+                character: '\t',
+                count: 2
+            }]
+        );
+        assert_eq!(result.len(), 6);
+        assert!(result.find_by_char('\n').is_some());
+        assert!(result.find_by_char('\u{7f}').is_some());
+        assert!(result.find_by_char('a').is_none());
+        assert!(result.find_by_char('[').is_none());
+    }
+
+    #[test]
+    fn count_chars_iter_method_test() {
+        let str = "Hello world! 你好，世界！";
+        assert_eq!(
+            str.count_chars_iter().collect::<Vec<_>>(),
+            str.count_chars()
+        );
+        assert_eq!(
+            str.count_chars_iter().take(2).collect::<Vec<_>>(),
+            str.count_chars().top_n(2)
+        );
+        let chars = str.chars().collect::<Vec<_>>();
+        assert_eq!(
+            chars.count_chars_iter().collect::<Vec<_>>(),
+            str.count_chars()
+        );
+        assert_eq!("".count_chars_iter().next(), None);
+
+        let mut iter = "Hello world!".count_chars_iter().take(2);
+        assert_eq!(
+            iter.next(),
+            Some(CharsCounter {
+                character: 'l',
+                count: 3
+            })
+        );
+        assert_eq!(
+            iter.next(),
+            Some(CharsCounter {
+                character: 'o',
+                count: 2
+            })
+        );
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn count_chars_from_iter_test() {
+        let result = count_chars_from_iter(vec!['a', 'a', 'b']);
+        assert_eq!(result, "aab".count_chars());
+
+        let result = count_chars_from_iter("Hello world!".chars().rev());
+        assert_eq!(result, "Hello world!".count_chars());
+    }
+
+    #[test]
+    fn ascii_digit_test() {
+        let str = "½٣12afG";
+        let result = str.count_chars_numeric();
+        assert_eq!(
+            result.iter().map(|x| x.character).collect::<String>(),
+            "12½٣"
+        );
+        let result = str.count_chars_ascii_digit();
+        assert_eq!(result.iter().map(|x| x.character).collect::<String>(), "12");
+        let result = str.count_chars_ascii_hexdigit();
+        assert_eq!(
+            result.iter().map(|x| x.character).collect::<String>(),
+            "12af"
+        );
+    }
+
+    #[test]
+    fn digit_radix_test() {
+        let str = "0127 89 aFgz½";
+        let result = str.count_chars_digit_radix(2);
+        assert_eq!(result.iter().map(|x| x.character).collect::<String>(), "01");
+        let result = str.count_chars_digit_radix(10);
+        assert_eq!(str.count_chars_ascii_digit(), result);
+        let result = str.count_chars_digit_radix(16);
+        assert_eq!(
+            result.iter().map(|x| x.character).collect::<String>(),
+            "012789Fa"
+        );
+        let result = str.count_chars_digit_radix(36);
+        assert_eq!(result.len(), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "radix must be in 2..=36")]
+    fn digit_radix_invalid_test() {
+        "123".count_chars_digit_radix(37);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() {
+        let result = "aab".count_chars();
+        let json = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"character":"a","count":2},{"character":"b","count":1}]"#
+        );
+        let back: Vec<CharsCounter> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, result);
+    }
+
+    #[test]
+    fn emoji_test() {
+        let str = "I \u{2764}\u{FE0F}\u{1F980}\u{1F980} #\u{FE0F}\u{20E3} \u{1F44D}\u{1F3FD}";
+        let result = str.count_chars_emoji();
+        assert_eq!(
+            result,
+            vec![
+                CharsCounter {
+                    character: '\u{1F980}',
+                    count: 2
+                },
+                CharsCounter {
+                    character: '\u{2764}',
+                    count: 1
+                },
+                CharsCounter {
+                    character: '\u{1F3FD}',
+                    count: 1
+                },
+                CharsCounter {
+                    character: '\u{1F44D}',
+                    count: 1
+                },
+            ]
+        );
+
+        let str = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467} \u{1F1EF}\u{1F1F5}";
+        let result = str.count_chars_emoji();
+        assert_eq!(result.total_count(), 5);
+        assert!(result.find_by_char('\u{200D}').is_none());
+    }
+
+    #[test]
+    fn to_histogram_test() {
+        let str = "aaab";
+        assert_eq!(
+            str.count_chars().to_histogram(9),
+            "'a' 3 #########\n'b' 1 ###\n"
+        );
+        assert_eq!(str.count_chars().to_histogram(4), "'a' 3 ####\n'b' 1 #\n");
+        let result = vec![CharsCounter {
+            character: 'z',
+            count: 0,
+        }];
+        assert_eq!(result.to_histogram(10), "'z' 0\n");
+
+        // A nonzero count that rounds down to nothing still gets one mark.
+        let result = vec![
+            CharsCounter {
+                character: 'a',
+                count: 100,
+            },
+            CharsCounter {
+                character: 'b',
+                count: 1,
+            },
+        ];
+        assert_eq!(result.to_histogram(10), "'a' 100 ##########\n'b' 1 #\n");
+        assert_eq!(result.to_histogram(0), "'a' 100\n'b' 1\n");
+        let result = vec![CharsCounter {
+            character: 'a',
+            count: usize::MAX,
+        }];
+        assert_eq!(result.to_histogram(3), format!("'a' {} ###\n", usize::MAX));
+        assert_eq!("".count_chars().to_histogram(10), "");
+    }
+
+    #[test]
+    fn to_csv_test() {
+        assert_eq!(
+            "Hello".count_chars().to_csv(),
+            "character,count\r\nl,2\r\nH,1\r\ne,1\r\no,1\r\n"
+        );
+        assert_eq!(
+            "a,,\n\"".count_chars().to_csv(),
+            "character,count\r\n\",\",2\r\n\"\n\",1\r\n\"\"\"\",1\r\na,1\r\n"
+        );
+        assert_eq!("".count_chars().to_csv(), "character,count\r\n");
+    }
+
+    #[test]
+    fn to_json_test() {
+        assert_eq!(
+            "aab".count_chars().to_json(),
+            r#"[{"character":"a","count":2},{"character":"b","count":1}]"#
+        );
+        assert_eq!(
+            "\"\\\n".count_chars().to_json(),
+            r#"[{"character":"\u000a","count":1},{"character":"\"","count":1},{"character":"\\","count":1}]"#
+        );
+        assert_eq!("".count_chars().to_json(), "[]");
+
+        let str = "Hé\u{1}\t\"\\🦀";
+        let value: serde_json::Value = serde_json::from_str(&str.count_chars().to_json()).unwrap();
+        let parsed = value
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|x| x["character"].as_str().unwrap())
+            .collect::<String>();
+        assert_eq!(
+            parsed,
+            str.count_chars()
+                .iter()
+                .map(|x| x.character)
+                .collect::<String>()
+        );
+    }
+
+    #[test]
+    fn top_n_test() {
+        let result = "Hello world!".count_chars();
+        assert_eq!(result.top_n(0), vec![]);
+        assert_eq!(
+            result.top_n(2),
+            vec![
+                CharsCounter {
+                    character: 'l',
+                    count: 3
+                },
+                CharsCounter {
+                    character: 'o',
+                    count: 2
+                }
+            ]
+        );
+        assert_eq!(result.top_n(result.len()), result);
+        assert_eq!(result.top_n(100), result);
+
+        // ' ' is kept and the six other characters counted once are cut.
+        assert_eq!(
+            result
+                .top_n(3)
+                .iter()
+                .map(|x| x.character)
+                .collect::<String>(),
+            "lo "
+        );
+        assert_eq!(
+            result
+                .bottom_n(2)
+                .iter()
+                .map(|x| x.character)
+                .collect::<String>(),
+            "wr"
+        );
+        assert_eq!(result.bottom_n(0), vec![]);
+        assert_eq!(result.bottom_n(100).len(), result.len());
+    }
+
+    #[test]
+    fn bottom_n_test() {
+        let result = "aaabbc".count_chars();
+        assert_eq!(result.bottom_n(0), vec![]);
+        assert_eq!(
+            result.bottom_n(2),
+            vec![
+                CharsCounter {
+                    character: 'c',
+                    count: 1
+                },
+                CharsCounter {
+                    character: 'b',
+                    count: 2
+                }
+            ]
+        );
+        let mut reversed = result.clone();
+        reversed.reverse();
+        assert_eq!(result.bottom_n(100), reversed);
+    }
+
+    #[test]
+    fn top_char_test() {
+        let result = "Hello world!".count_chars().top_char();
+        assert_eq!(
+            result,
+            Some(CharsCounter {
+                character: 'l',
+                count: 3
+            })
+        );
+        assert_eq!("baab".count_chars().top_char().unwrap().character, 'a');
+        assert_eq!("".count_chars().top_char(), None);
+    }
+
+    #[test]
+    fn bottom_char_test() {
+        let result = "aabbc".count_chars().bottom_char();
+        assert_eq!(
+            result,
+            Some(CharsCounter {
+                character: 'c',
+                count: 1
+            })
+        );
+        assert_eq!(
+            "Hello world!"
+                .count_chars()
+                .bottom_char()
+                .unwrap()
+                .character,
+            'w'
+        );
+        assert_eq!("".count_chars().bottom_char(), None);
+    }
+
+    #[test]
+    fn japanese_test() {
+        let str = "こんにちは、世界！カタカナ ｶﾀｶﾅ";
+        let result = str.count_chars_hiragana();
+        assert_eq!(
+            result.iter().map(|x| x.character).collect::<String>(),
+            "こちにはん"
+        );
+        let result = str.count_chars_katakana();
+        assert_eq!(result.find_by_char('カ').unwrap().count, 2);
+        assert_eq!(result.find_by_char('ｶ').unwrap().count, 2);
+        assert_eq!(result.total_count(), 8);
+        assert!(result.find_by_char('世').is_none());
+
+        let result = str.count_chars_japanese();
+        assert_eq!(result.total_count(), 15);
+        assert!(result.find_by_char('世').is_some());
+        assert!(result.find_by_char('、').is_none());
+        assert_eq!(str.count_chars_chinese().total_count(), 2);
+
+        let str = "ひらがなカタカナ";
+        let hiragana = str.count_chars_hiragana();
+        let katakana = str.count_chars_katakana();
+        assert_eq!(hiragana.total_count(), 4);
+        assert_eq!(katakana.total_count(), 4);
+        let mut kana = [hiragana, katakana].concat();
+        kana.sort();
+        assert_eq!(str.count_chars_japanese(), kana);
+    }
+
+    #[test]
+    fn hangul_test() {
+        let str = "안녕하세요 hello 123";
+        let result = str.count_chars_hangul();
+        assert_eq!(
+            result.iter().map(|x| x.character).collect::<String>(),
+            "녕세안요하"
+        );
+        assert_eq!(result.total_count(), 5);
+
+        let str = "\u{1100}\u{1161}ㄱㅏ";
+        assert_eq!(str.count_chars_hangul().total_count(), 4);
+    }
+
+    #[test]
+    fn cyrillic_test() {
+        let str = "Привіт, ґанок! Hello";
+        let result = str.count_chars_cyrillic();
+        assert_eq!(result.total_count(), 11);
+        assert!(result.find_by_char('ґ').is_some());
+        assert!(result.find_by_char('H').is_none());
+
+        // Latin 'a' and Cyrillic 'а' look alike but are different characters.
+        let str = "a\u{0430}\u{0430}";
+        let result = str.count_chars_cyrillic();
+        assert_eq!(
+            result,
+            vec![CharsCounter {
+                character: '\u{0430}',
+                count: 2
+            }]
+        );
+        assert_eq!(str.count_chars().find_by_char('a').unwrap().count, 1);
+    }
+
+    #[test]
+    fn to_map_test() {
+        let result = "Hello world!".count_chars();
+        let map = result.to_map();
+        assert_eq!(map.len(), result.len());
+        for c in ['l', 'o', 'H', '!'] {
+            assert_eq!(map[&c], result.find_by_char(c).unwrap().count);
+        }
+        assert!(!map.contains_key(&'z'));
+        assert!("".count_chars().to_map().is_empty());
+    }
+
+    #[test]
+    fn entropy_test() {
+        assert_eq!("ab".count_chars().entropy(), 1.0);
+        assert_eq!("aaaa".count_chars().entropy().to_string(), "0");
+        assert_eq!("".count_chars().entropy(), 0.0);
+
+        assert!(("abcd".count_chars().entropy() - 2.0).abs() < 1e-12);
+        let expected =
+            0.0 - (2.0 / 3.0) * (2.0f64 / 3.0).log2() - (1.0 / 3.0) * (1.0f64 / 3.0).log2();
+        assert!(("aab".count_chars().entropy() - expected).abs() < 1e-12);
+        assert!(("aab".count_chars().entropy() - 0.918_295_834_054_489_6).abs() < 1e-12);
+        let str = "0123456789abcdef";
+        assert!((str.count_chars().entropy() - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn greek_test() {
+        let str = "μῆνιν ἄειδε θεά (Iliad 1.1, sing goddess)";
+        let result = str.count_chars_greek();
+        assert_eq!(result.total_count(), 13);
+        assert_eq!(result.find_by_char('ε').unwrap().count, 3);
+        assert!(result.find_by_char('ἄ').is_some());
+        assert!(result.find_by_char('ά').is_some());
+        assert!(result.find_by_char('ῆ').is_some());
+        assert!(result.iter().all(|x| !x.character.is_ascii()));
+    }
+
+    #[test]
+    fn arabic_test() {
+        let str = "مرحبا hello ٣٤ 34 \u{FEFB}";
+        let result = str.count_chars_arabic();
+        assert_eq!(result.total_count(), 8);
+        assert!(result.find_by_char('٣').is_some());
+        assert!(result.find_by_char('\u{FEFB}').is_some());
+        assert!(result.find_by_char('3').is_none());
+        assert!(result.find_by_char('h').is_none());
+    }
+
+    #[cfg(feature = "normalization")]
+    #[test]
+    fn normalized_test() {
+        use crate::NormalizationForm;
+
+        let composed = "caf\u{e9} \u{e9}t\u{e9}";
+        let decomposed = "cafe\u{301} e\u{301}te\u{301}";
+        assert_ne!(composed.count_chars(), decomposed.count_chars());
+        assert_eq!(
+            composed.count_chars_normalized(NormalizationForm::Nfc),
+            decomposed.count_chars_normalized(NormalizationForm::Nfc)
+        );
+        assert_eq!(
+            decomposed.count_chars_normalized(NormalizationForm::Nfc)[0],
+            CharsCounter {
+                character: '\u{e9}',
+                count: 3
+            }
+        );
+        assert_eq!(
+            composed.count_chars_normalized(NormalizationForm::Nfd),
+            decomposed.count_chars()
+        );
+        assert_eq!(
+            "\u{FB01}".count_chars_normalized(NormalizationForm::Nfkc),
+            "fi".count_chars()
+        );
+
+        let composed = "\u{D55C}\u{AE00}";
+        let decomposed = "\u{1112}\u{1161}\u{11AB}\u{1100}\u{1173}\u{11AF}";
+        assert_eq!(
+            decomposed.count_chars_normalized(NormalizationForm::Nfc),
+            composed.count_chars()
+        );
+        assert_eq!(
+            composed.count_chars_normalized(NormalizationForm::Nfd),
+            decomposed.count_chars()
+        );
+    }
+
+    #[test]
+    fn count_chars_in_script_test() {
+        let str = "Hi! Привет γειά مرحبا שלום नमस्ते 你好 ひらがな カタカナ 안녕 สวัสดี";
+        let scripts = [
+            (Script::Latin, 2),
+            (Script::Cyrillic, 6),
+            (Script::Greek, 4),
+            (Script::Arabic, 5),
+            (Script::Hebrew, 4),
+            (Script::Devanagari, 6),
+            (Script::Han, 2),
+            (Script::Hiragana, 4),
+            (Script::Katakana, 4),
+            (Script::Hangul, 2),
+            (Script::Thai, 6),
+        ];
+        for (script, total) in scripts {
+            let result = str.count_chars_in_script(script);
+            assert_eq!(result.total_count(), total, "{:?}", script);
+            assert!(result.iter().all(|x| script.contains(x.character)));
+        }
+        for c in str.chars() {
+            let matched = scripts.iter().filter(|x| x.0.contains(c)).count();
+            assert!(matched <= 1, "{:?}", c);
+        }
+        assert!(str
+            .count_chars_in_script(Script::Latin)
+            .find_by_char('!')
+            .is_none());
+
+        let str = "Привет, ґанок! Μῆνιν ἄειδε";
+        assert_eq!(
+            str.count_chars_in_script(Script::Cyrillic),
+            str.count_chars_cyrillic()
+        );
+        assert_eq!(
+            str.count_chars_in_script(Script::Greek),
+            str.count_chars_greek()
+        );
+        assert_eq!(str.count_chars_in_script(Script::Greek).total_count(), 10);
+    }
+
+    #[test]
+    fn display_test() {
+        let counter = CharsCounter {
+            character: 'l',
+            count: 3,
+        };
+        assert_eq!(counter.to_string(), "'l': 3");
+        assert_eq!(
+            format!("{:?}", counter),
+            "CharsCounter { character: 'l', count: 3 }"
+        );
+    }
+
+    #[test]
+    fn chinese_test() {
+        let str = "你好，世界。\u{3400}\u{20000}\u{2A6DF}\u{F900}";
+        let result = str.count_chars_chinese();
+        assert_eq!(result.total_count(), 8);
+        assert!(result.find_by_char('\u{3400}').is_some());
+        assert!(result.find_by_char('\u{20000}').is_some());
+        assert!(result.find_by_char('，').is_none());
+        assert!(result.find_by_char('。').is_none());
+    }
+
+    #[test]
+    fn ord_test() {
+        let expected = "Hello world!".count_chars();
+        let mut result = expected.clone();
+        result.reverse();
+        result.swap(1, 5);
+        result.swap(0, 3);
+        assert_ne!(result, expected);
+        result.sort();
+        assert_eq!(result, expected);
+
+        assert_eq!(expected.iter().min(), expected.first());
+        assert_eq!(expected.iter().max(), expected.last());
+        let set = expected
+            .iter()
+            .copied()
+            .collect::<std::collections::BTreeSet<_>>();
+        assert_eq!(set.into_iter().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn count_chars_in_range_test() {
+        let str = "┌─┐\n└─┘ abc \u{E000}";
+        let result = str.count_chars_in_range('\u{2500}'..='\u{257F}');
+        assert_eq!(result.total_count(), 6);
+        assert_eq!(result[0].character, '─');
+        assert_eq!(str.count_chars_in_range('b'..='b').total_count(), 1);
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = 'z'..='a';
+        assert!(str.count_chars_in_range(empty).is_empty());
+
+        let result = str.count_chars_in_ranges(&['a'..='b', '\u{E000}'..='\u{F8FF}']);
+        assert_eq!(
+            result.iter().map(|x| x.character).collect::<String>(),
+            "ab\u{E000}"
+        );
+        assert!(str.count_chars_in_ranges(&[]).is_empty());
+
+        let str = "中文\u{3400}\u{20000}";
+        let result = str.count_chars_in_range('\u{4E00}'..='\u{9FFF}');
+        assert_eq!(result.total_count(), 2);
+        let result = str.count_chars_in_range('\u{20000}'..='\u{2A6DF}');
+        assert_eq!(result[0].character, '\u{20000}');
+        #[allow(clippy::reversed_empty_ranges)]
+        let inverted = '\u{9FFF}'..='\u{4E00}';
+        assert!(str.count_chars_in_range(inverted).is_empty());
+    }
+
+    #[test]
+    fn vowels_test() {
+        let str = "Education by Yoyo";
+        let result = str.count_chars_vowels();
+        assert_eq!(
+            result.iter().map(|x| x.character).collect::<String>(),
+            "oEaiu"
+        );
+        assert_eq!(result.find_by_char('o').unwrap().count, 3);
+        let result = str.count_chars_vowels_with_y();
+        assert_eq!(result.find_by_char('y').unwrap().count, 2);
+        assert_eq!(result.find_by_char('Y').unwrap().count, 1);
+        assert!("rhythm".count_chars_vowels().is_empty());
+    }
+
+    #[test]
+    fn count_chars_in_set_test() {
+        let dna = "ACGTTGCANNACGT";
+        let result = dna.count_chars_in_set("ACGTTA");
+        assert_eq!(result.total_count(), 12);
+        assert!(result.find_by_char('N').is_none());
+        assert_eq!(result.find_by_char('A').unwrap().count, 3);
+
+        let str = "über café naïve";
+        let result = str.count_chars_in_set("éü");
+        assert_eq!(result.iter().map(|x| x.character).collect::<String>(), "éü");
+        assert!(str.count_chars_in_set("").is_empty());
+
+        let str = "The quick brown fox jumps over the lazy dog";
+        let set = HashSet::from(['a', 'e', 'i']);
+        let result = str.count_chars_in_set(&set);
+        assert_eq!(
+            result
+                .iter()
+                .map(|x| (x.character, x.count))
+                .collect::<Vec<_>>(),
+            [('e', 3), ('a', 1), ('i', 1)]
+        );
+        assert_eq!(
+            str.count_chars_in_set(&BTreeSet::from(['a', 'e', 'i'])),
+            result
+        );
+        assert_eq!(str.count_chars_in_set(&['a', 'e', 'i'][..]), result);
+        assert_eq!(str.count_chars_in_set("aei"), result);
+        assert!(str.count_chars_in_set(&HashSet::new()).is_empty());
+
+        let set = ('\u{4e00}'..'\u{9fa6}').step_by(2).collect::<String>();
+        let text = ('\u{4e00}'..'\u{9fa6}')
+            .chain('a'..='z')
+            .collect::<String>();
+        let hashed = set.chars().collect::<HashSet<_>>();
+        let result = text.count_chars_in_set(set.as_str());
+        assert_eq!(result, text.count_chars_in_set(&hashed));
+        assert_eq!(result.total_count(), set.chars().count());
+        assert_eq!(
+            text.count_chars_except(set.as_str()),
+            text.count_chars_filter(|x| !hashed.contains(x))
+        );
+        let chars = set.chars().collect::<Vec<_>>();
+        assert_eq!(text.count_chars_in_set(&chars[..]), result);
+    }
+
+    #[test]
+    fn consonants_test() {
+        assert_eq!(
+            "sky".count_chars_consonants(),
+            vec![
+                CharsCounter {
+                    character: 'k',
+                    count: 1
+                },
+                CharsCounter {
+                    character: 's',
+                    count: 1
+                },
+                CharsCounter {
+                    character: 'y',
+                    count: 1
+                }
+            ]
+        );
+        let result = "Abc, 123 DÉ!".count_chars_consonants();
+        assert_eq!(
+            result.iter().map(|x| x.character).collect::<String>(),
+            "DbcÉ"
+        );
+    }
+
+    #[test]
+    fn count_chars_except_test() {
+        let str = "Hello, world.\tBye;\n";
+        let excluded = " \t\n.,;:";
+        let result = str.count_chars_except(excluded);
+        assert!(result.iter().all(|x| !excluded.contains(x.character)));
+        let all = str.count_chars();
+        for x in &result {
+            assert_eq!(all.find_by_char(x.character), Some(*x));
+        }
+        assert!(result.find_by_char('l').is_some());
+        assert_eq!(str.count_chars_except(""), all);
+        // The set is a set of characters, not a substring to match.
+        assert!("ab ba"
+            .count_chars_except("ab")
+            .iter()
+            .all(|x| x.character == ' '));
+    }
+
+    #[cfg(feature = "casefold")]
+    #[test]
+    fn casefold_test() {
+        let result = "ßẞſS".count_chars_casefold();
+        assert_eq!(
+            result,
+            vec![CharsCounter {
+                character: 's',
+                count: 6
+            }]
+        );
+
+        let result = "Σσς".count_chars_casefold();
+        assert_eq!(
+            result,
+            vec![CharsCounter {
+                character: 'σ',
+                count: 3
+            }]
+        );
+
+        let result = "İIıi".count_chars_casefold();
+        assert_eq!(result.find_by_char('i').unwrap().count, 3);
+        assert_eq!(result.find_by_char('ı').unwrap().count, 1);
+        assert_eq!(result.find_by_char('\u{307}').unwrap().count, 1);
+        assert!(result.find_by_char('I').is_none());
+    }
+
+    #[cfg(feature = "normalization")]
+    #[test]
+    fn strip_accents_test() {
+        let composed = "café naïve";
+        let decomposed = "cafe\u{301} nai\u{308}ve";
+        let result = composed.count_chars_strip_accents();
+        assert_eq!(result, decomposed.count_chars_strip_accents());
+        assert_eq!(result, "cafe naive".count_chars());
+        assert_eq!(result.find_by_char('a').unwrap().count, 2);
+        assert_eq!(result.find_by_char('e').unwrap().count, 2);
+        assert_eq!(result.find_by_char('i').unwrap().count, 1);
+        assert!(result.iter().all(|x| x.character.is_ascii()));
+
+        assert_eq!("ø".count_chars_strip_accents(), "ø".count_chars());
+
+        let result = "résumé".count_chars_strip_accents();
+        assert_eq!(result, "resume".count_chars());
+        assert_eq!(result.find_by_char('e').unwrap().count, 2);
+        assert!(result.find_by_char('é').is_none());
+        assert_eq!("Straße".count_chars_strip_accents(), "Straße".count_chars());
+    }
+
+    #[test]
+    fn count_chars_by_key_test() {
+        let str = "Call 555-0123 or 555-0199";
+        let result = str.count_chars_by_key(|x| if x.is_ascii_digit() { '#' } else { x });
+        assert_eq!(
+            result[0],
+            CharsCounter {
+                character: '#',
+                count: 14
+            }
+        );
+        assert_eq!(result.total_count(), str.chars().count());
+        let result = str.count_chars_by_key(|x| x.to_ascii_lowercase());
+        assert_eq!(result.find_by_char('c').unwrap().count, 1);
+        assert_eq!(result.find_by_char('l').unwrap().count, 2);
+
+        let result = str.count_chars_filter_key(
+            |x| x.is_ascii_digit(),
+            |x| {
+                if x < '5' {
+                    'l'
+                } else {
+                    'h'
+                }
+            },
+        );
+        assert_eq!(
+            result,
+            vec![
+                CharsCounter {
+                    character: 'h',
+                    count: 8
+                },
+                CharsCounter {
+                    character: 'l',
+                    count: 6
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_test() {
+        let result = "aab".count_chars().merge(&"bcc".count_chars());
+        assert_eq!(result.find_by_char('b').unwrap().count, 2);
+        assert_eq!(result, "aabbcc".count_chars());
+        assert_eq!(
+            "".count_chars().merge(&"ab".count_chars()),
+            "ab".count_chars()
+        );
+
+        let result = ["Hello", " ", "world!"]
+            .iter()
+            .fold(Vec::new(), |acc, x| acc.merge(&x.count_chars()));
+        assert_eq!(result, "Hello world!".count_chars());
+    }
+
+    #[test]
+    fn subtract_test() {
+        let result = "aab".count_chars().subtract(&"ab".count_chars());
+        assert_eq!(
+            result,
+            vec![CharsCounter {
+                character: 'a',
+                count: 1
+            }]
+        );
+        let result = "abbbc".count_chars().subtract(&"bzz".count_chars());
+        assert_eq!(result, "abbc".count_chars());
+        assert!("ab"
+            .count_chars()
+            .subtract(&"aabbb".count_chars())
+            .is_empty());
+    }
+
+    #[test]
+    fn per_line_test() {
+        let str = "aab\r\n\r\nb c\nlast";
+        let result = str.count_chars_per_line();
+        assert_eq!(result.len(), 4);
+        assert_eq!(result[0], "aab".count_chars());
+        assert!(result[1].is_empty());
+        assert_eq!(result[2], "b c".count_chars());
+        assert_eq!(result[3], "last".count_chars());
+        assert!(result.iter().all(|x| x.find_by_char('\r').is_none()));
+
+        let result = str.count_chars_lines_filter(|x| *x == 'b');
+        assert_eq!(
+            result.iter().map(|x| x.total_count()).collect::<Vec<_>>(),
+            [1, 0, 1, 0]
+        );
+        assert!("".count_chars_per_line().is_empty());
+    }
+
+    #[test]
+    fn contains_char_test() {
+        let result = "Hello world!".count_chars();
+        assert!(result.contains_char('H'));
+        assert!(!result.contains_char('h'));
+    }
+
+    #[test]
+    fn count_of_test() {
+        let result = "Hello world!".count_chars();
+        assert_eq!(result.count_of('l'), 3);
+        assert_eq!(result.count_of('z'), 0);
+    }
+
+    #[test]
+    fn distinct_count_test() {
+        assert_eq!("aabbbc".count_chars().distinct_count(), 3);
+        assert_eq!("a1b2".count_chars_numeric().distinct_count(), 2);
+        assert_eq!("".count_chars().distinct_count(), 0);
+    }
+
+    #[test]
+    fn utf16_test() {
+        let str = "a\u{1F980}\u{1F980}中";
+        let units = str.encode_utf16().collect::<Vec<_>>();
+        assert_eq!(units.len(), 6);
+        assert_eq!(count_chars_utf16(&units), str.count_chars());
+        assert_eq!(count_chars_utf16_strict(&units).unwrap(), str.count_chars());
+
+        let units = [0x61, 0xD800, 0x62];
+        assert_eq!(count_chars_utf16(&units), "a\u{FFFD}b".count_chars());
+        let error = count_chars_utf16_strict(&units).unwrap_err();
+        assert_eq!(error.unpaired_surrogate(), 0xD800);
+    }
+
+    #[test]
+    fn count_chars_sorted_test() {
+        let str = "bcaab";
+        let chars = |order| {
+            str.count_chars_sorted(order)
+                .iter()
+                .map(|x: &CharsCounter| x.character)
+                .collect::<String>()
+        };
+        assert_eq!(
+            str.count_chars_sorted(SortOrder::CountDesc),
+            str.count_chars()
+        );
+        assert_eq!(chars(SortOrder::CountDesc), "abc");
+        assert_eq!(chars(SortOrder::CountAsc), "cab");
+        assert_eq!(chars(SortOrder::CharAsc), "abc");
+        assert_eq!(chars(SortOrder::CharDesc), "cba");
+    }
+
+    #[test]
+    fn ranked_test() {
+        let result = "aabbc".count_chars().ranked();
+        assert_eq!(
+            result
+                .iter()
+                .map(|x| (x.0, x.1.character))
+                .collect::<Vec<_>>(),
+            [(1, 'a'), (1, 'b'), (3, 'c')]
+        );
+        assert!("".count_chars().ranked().is_empty());
+    }
+
+    #[test]
+    fn count_chars_ascii_path_test() {
+        let str = "The quick brown fox jumps over the lazy dog. 0123456789\n".repeat(20_000);
+        let result = str.count_chars();
+        assert_eq!(result, str.count_chars_filter(|_| true));
+        assert_eq!(result, str.count_chars_ascii_fast());
+        assert_eq!(result.total_count(), str.len());
+
+        let str = "aé🦀a ё\u{7f}\u{80}é🦀🦀 z";
+        assert_eq!(str.count_chars(), str.count_chars_filter(|_| true));
+        assert_eq!("ééé".count_chars(), "ééé".count_chars_filter(|_| true));
+        assert!("".count_chars().is_empty());
+    }
+
+    #[test]
+    fn chars_iter_only_impl_test() {
+        struct Letters(&'static str);
+
+        impl ICharsCounter for Letters {
+            fn chars_iter(&self) -> impl Iterator<Item = char> + '_ {
+                self.0.chars().filter(|x| x.is_alphabetic())
+            }
+        }
+
+        let letters = Letters("Hello, 世界!");
+        assert_eq!(letters.count_chars(), "Hello世界".count_chars());
+        #[cfg(feature = "rayon")]
+        assert_eq!(
+            letters.count_chars_filter_parallel(|x| x.is_ascii()),
+            "Hello".count_chars()
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn count_chars_parallel_test() {
+        let str = "Hello world! 你好，世界！🦀 é".repeat(50_000);
+        assert_eq!(str.count_chars_parallel(), str.count_chars());
+        assert_eq!("".count_chars_parallel(), vec![]);
+        assert_eq!("aé🦀".count_chars_parallel(), "aé🦀".count_chars());
+
+        assert_eq!(
+            str.count_chars_filter_parallel(|x| !x.is_ascii()),
+            str.count_chars_filter(|x| !x.is_ascii())
+        );
+        let chars = str.chars().collect::<Vec<_>>();
+        assert_eq!(
+            chars.count_chars_filter_parallel(|x| x.is_alphabetic()),
+            str.count_chars_alphabetic()
+        );
+
+        let chunks = super::split_chunks("aé🦀b", 2);
+        assert_eq!(chunks, ["aé", "🦀", "b"]);
+    }
+
+    #[test]
+    fn char_slice_test() {
+        let str = "héllo\r\nwörld\t 123\n\nlast\r";
+        let chars = str.chars().collect::<Vec<_>>();
+        assert_eq!(chars.count_chars(), str.count_chars());
+        assert_eq!(
+            "héllo".chars().collect::<Vec<_>>().count_chars(),
+            "héllo".count_chars()
+        );
+        assert_eq!(chars.count_chars_alphabetic(), str.count_chars_alphabetic());
+        assert_eq!(
+            chars.count_chars_no_whitespace(),
+            str.count_chars_no_whitespace()
+        );
+        assert_eq!(
+            chars.count_chars_ignore_case(),
+            str.count_chars_ignore_case()
+        );
+        assert_eq!(
+            chars.count_chars_in_script(Script::Latin),
+            str.count_chars_in_script(Script::Latin)
+        );
+        assert_eq!(
+            chars.count_chars_filter(|x| *x > 'l'),
+            str.count_chars_filter(|x| *x > 'l')
+        );
+        assert_eq!(chars.count_chars_per_line(), str.count_chars_per_line());
+        assert_eq!(chars[..0].count_chars(), vec![]);
+        #[cfg(feature = "rayon")]
+        assert_eq!(chars.count_chars_parallel(), str.count_chars_parallel());
+    }
+
+    #[test]
+    fn per_line_matches_lines_test() {
+        for str in [
+            "a\r",
+            "a\n",
+            "",
+            "\n",
+            "a\r\r\nb",
+            "\r\n",
+            "a\rb",
+            "x\n\ny\r\n",
+        ] {
+            let expected = str
+                .lines()
+                .map(|x| x.chars().collect::<Vec<_>>().count_chars())
+                .collect::<Vec<_>>();
+            assert_eq!(str.count_chars_per_line(), expected, "{:?}", str);
+        }
+    }
+
+    #[test]
+    fn iter_chars_counter_test() {
+        let result = "Hello".chars().chain(" world!".chars()).count_chars();
+        assert_eq!(
+            result,
+            "Hello".count_chars().merge(&" world!".count_chars())
+        );
+        assert_eq!(result, "Hello world!".count_chars());
+
+        let result = "Hello world!"
+            .chars()
+            .rev()
+            .count_chars_filter(|x| x.is_alphabetic());
+        assert_eq!(result, "Hello world!".count_chars_alphabetic());
+        assert!(std::iter::empty::<char>().count_chars().is_empty());
+    }
+
+    #[test]
+    fn ascii_fast_test() {
+        let str = "Hello wörld! 你好, 🦀 123\u{7f}\u{0}";
+        assert_eq!(str.count_chars_ascii_fast(), str.count_chars_ascii());
+        let chars = str.chars().collect::<Vec<_>>();
+        assert_eq!(chars.count_chars_ascii_fast(), str.count_chars_ascii());
+        assert!("".count_chars_ascii_fast().is_empty());
+
+        let str = "The quick brown fox jumps over the lazy dog. ".repeat(200_000);
+        let result = str.count_chars_ascii_fast();
+        assert_eq!(result, str.count_chars_ascii());
+        assert_eq!(result.total_count(), str.len());
+    }
+
+    #[test]
+    fn count_chars_regression_test() {
+        let counter = |character, count| CharsCounter { character, count };
+        assert_eq!(
+            "Hello world!".count_chars(),
+            vec![
+                counter('l', 3),
+                counter('o', 2),
+                counter(' ', 1),
+                counter('!', 1),
+                counter('H', 1),
+                counter('d', 1),
+                counter('e', 1),
+                counter('r', 1),
+                counter('w', 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn os_str_test() {
+        let name = std::ffi::OsStr::new("café.txt");
+        assert_eq!(name.count_chars(), "café.txt".count_chars());
+        #[cfg(feature = "rayon")]
+        assert_eq!(name.count_chars_parallel(), "café.txt".count_chars());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+
+            let name = std::ffi::OsStr::from_bytes(b"a\xFFb\xFE");
+            let result = name.count_chars();
+            assert_eq!(result, "a\u{FFFD}b\u{FFFD}".count_chars());
+            assert_eq!(result.count_of('\u{FFFD}'), 2);
+        }
+    }
+
+    #[test]
+    fn deref_str_test() {
+        fn as_ref_counter<T: AsRef<str>>(text: T) -> Vec<CharsCounter> {
+            text.as_ref().count_chars()
+        }
+        fn generic_counter<T: ICharsCounter + ?Sized>(text: &T) -> Vec<CharsCounter> {
+            text.count_chars()
+        }
+
+        let expected = "Hello world!".count_chars();
+        let boxed: Box<str> = "Hello world!".into();
+        let rc: std::rc::Rc<str> = "Hello world!".into();
+        let arc: std::sync::Arc<str> = "Hello world!".into();
+        assert_eq!(boxed.count_chars(), expected);
+        assert_eq!(rc.count_chars(), expected);
+        assert_eq!(arc.count_chars(), expected);
+        assert_eq!(as_ref_counter("Hello world!"), expected);
+        assert_eq!(as_ref_counter(String::from("Hello world!")), expected);
+        assert_eq!(generic_counter("Hello world!"), expected);
+        assert_eq!(generic_counter(&*boxed), expected);
+        assert_eq!(generic_counter(&['H', 'i'][..]), "Hi".count_chars());
+    }
 }
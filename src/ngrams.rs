@@ -0,0 +1,118 @@
+use crate::counter::{cmp_counters, into_sorted};
+use crate::Map;
+use crate::{Counter, ICounterExt};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// The count of one n-gram, a run of `n` adjacent characters.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NgramCounter {
+    pub gram: String,
+    pub count: usize,
+}
+
+impl Counter for NgramCounter {
+    type Key = str;
+
+    fn key(&self) -> &str {
+        &self.gram
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl Ord for NgramCounter {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_counters(self, other)
+    }
+}
+
+impl PartialOrd for NgramCounter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub trait INgramsCounter {
+    /// Counts the n-grams of a window sliding one character at a time, so windows
+    /// overlap and "aaa" holds two "aa" bigrams. `n == 0`, or `n` longer than the
+    /// text, gives an empty result.
+    fn count_ngrams(&self, n: usize) -> Vec<NgramCounter>;
+}
+
+/// Queries on the result of `count_ngrams`, with `find_by_gram` looking an n-gram up by its text.
+pub trait INgramCounterExt {
+    fn most_ngrams(&self) -> Vec<NgramCounter>;
+    fn least_ngrams(&self) -> Vec<NgramCounter>;
+    fn find_by_gram(&self, gram: &str) -> Option<NgramCounter>;
+}
+
+impl INgramsCounter for str {
+    fn count_ngrams(&self, n: usize) -> Vec<NgramCounter> {
+        let bounds = self
+            .char_indices()
+            .map(|x| x.0)
+            .chain(core::iter::once(self.len()))
+            .collect::<Vec<_>>();
+        if n == 0 || n >= bounds.len() {
+            return Vec::new();
+        }
+        let mut counts = Map::<&str, usize>::new();
+        for w in bounds.windows(n + 1) {
+            *counts.entry(&self[w[0]..w[n]]).or_insert(0) += 1;
+        }
+        into_sorted(counts, |gram, count| NgramCounter {
+            gram: gram.to_string(),
+            count,
+        })
+    }
+}
+
+impl INgramCounterExt for Vec<NgramCounter> {
+    fn most_ngrams(&self) -> Vec<NgramCounter> {
+        self.most()
+    }
+
+    fn least_ngrams(&self) -> Vec<NgramCounter> {
+        self.least()
+    }
+
+    fn find_by_gram(&self, gram: &str) -> Option<NgramCounter> {
+        self.find_by_key(gram)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{ICharsCounter, ICounterExt, INgramCounterExt, INgramsCounter};
+
+    #[test]
+    fn count_ngrams_test() {
+        let str = "hello";
+        let result = str.count_ngrams(2);
+        assert_eq!(result.len(), 4);
+        assert_eq!(result.find_by_gram("ll").unwrap().count, 1);
+        assert_eq!(result.find_by_gram("lo").unwrap().count, 1);
+        assert_eq!(result.find_by_num(1).len(), 4);
+
+        let result = "aaa".count_ngrams(2);
+        assert_eq!(result.most_ngrams()[0].count, 2);
+
+        let unigrams = "héllo wörld".count_ngrams(1);
+        let chars = "héllo wörld".count_chars();
+        assert_eq!(unigrams.len(), chars.len());
+        for (gram, c) in unigrams.iter().zip(chars.iter()) {
+            assert_eq!(gram.gram, c.character.to_string());
+            assert_eq!(gram.count, c.count);
+        }
+
+        assert_eq!("héllo".count_ngrams(5)[0].gram, "héllo");
+        assert!("hello".count_ngrams(0).is_empty());
+        assert!("hello".count_ngrams(6).is_empty());
+        assert!("".count_ngrams(1).least_ngrams().is_empty());
+    }
+}
@@ -0,0 +1,264 @@
+use crate::{into_counters, CharsCounter, Map};
+use std::io::{self, BufRead, ErrorKind, Read};
+use std::str;
+
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Per-line and overall counts returned by `count_chars_by_lines`.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineCounts {
+    pub per_line: Vec<Vec<CharsCounter>>,
+    pub total: Vec<CharsCounter>,
+}
+
+/// Counts the characters of a UTF-8 stream chunk by chunk, sorted like `count_chars`.
+///
+/// Sequences split across reads are stitched back together. Invalid UTF-8,
+/// including a truncated sequence at the end of the stream, is reported as
+/// an `ErrorKind::InvalidData` error.
+///
+/// ```
+/// use chars_counter::count_chars_reader;
+/// use std::io::Cursor;
+///
+/// let result = count_chars_reader(Cursor::new("añña")).unwrap();
+/// assert_eq!(result[0].character, 'a');
+/// assert_eq!(result[1].character, 'ñ');
+/// ```
+pub fn count_chars_reader<R: Read>(reader: R) -> io::Result<Vec<CharsCounter>> {
+    read_counts(reader, CHUNK_SIZE, false, |_| true)
+}
+
+/// Counts the characters of a UTF-8 stream that satisfy `predicate`, erroring like `count_chars_reader`.
+pub fn count_chars_reader_filter<R, P>(reader: R, predicate: P) -> io::Result<Vec<CharsCounter>>
+where
+    R: Read,
+    P: FnMut(&char) -> bool,
+{
+    read_counts(reader, CHUNK_SIZE, false, predicate)
+}
+
+/// Counts the characters of a UTF-8 stream, replacing invalid sequences with U+FFFD.
+///
+/// The result matches counting `String::from_utf8_lossy` of the whole stream;
+/// only read failures are returned as errors.
+pub fn count_chars_reader_lossy<R: Read>(reader: R) -> io::Result<Vec<CharsCounter>> {
+    read_counts(reader, CHUNK_SIZE, true, |_| true)
+}
+
+/// Counts a buffered stream line by line, keeping only one line in memory.
+///
+/// Lines are split like `str::lines`: the "\n" or "\r\n" terminator is not
+/// counted, in `per_line` or in `total`. The first read failure is returned.
+///
+/// ```
+/// use chars_counter::{count_chars_by_lines, ICharsCounter};
+/// use std::io::Cursor;
+///
+/// let result = count_chars_by_lines(Cursor::new("ab\r\nb\n")).unwrap();
+/// assert_eq!(result.per_line, vec!["ab".count_chars(), "b".count_chars()]);
+/// assert_eq!(result.total, "abb".count_chars());
+/// ```
+pub fn count_chars_by_lines<R: BufRead>(mut reader: R) -> io::Result<LineCounts> {
+    let mut per_line = Vec::new();
+    let mut total = Map::<char, usize>::new();
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 0 {
+        let text = line
+            .strip_suffix('\n')
+            .map_or(line.as_str(), |x| x.strip_suffix('\r').unwrap_or(x));
+        let mut counts = Map::<char, usize>::new();
+        for c in text.chars() {
+            *counts.entry(c).or_insert(0) += 1;
+            *total.entry(c).or_insert(0) += 1;
+        }
+        per_line.push(into_counters(counts));
+        line.clear();
+    }
+    Ok(LineCounts {
+        per_line,
+        total: into_counters(total),
+    })
+}
+
+fn read_counts<R, P>(
+    mut reader: R,
+    chunk_size: usize,
+    lossy: bool,
+    mut predicate: P,
+) -> io::Result<Vec<CharsCounter>>
+where
+    R: Read,
+    P: FnMut(&char) -> bool,
+{
+    let mut counts = Map::<char, usize>::new();
+    let mut add = |c: char| {
+        if predicate(&c) {
+            *counts.entry(c).or_insert(0) += 1;
+        }
+    };
+    let mut buf = vec![0u8; chunk_size];
+    // Bytes of an incomplete sequence carried over to the start of `buf`.
+    let mut pending = 0;
+    loop {
+        let n = match reader.read(&mut buf[pending..]) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        let filled = pending + n;
+        let mut start = 0;
+        for chunk in buf[..filled].utf8_chunks() {
+            chunk.valid().chars().for_each(&mut add);
+            start += chunk.valid().len();
+            let invalid = chunk.invalid();
+            if invalid.is_empty() {
+                continue;
+            }
+            // Only the last chunk can end in a sequence cut short by the read.
+            let truncated = str::from_utf8(invalid).is_err_and(|e| e.error_len().is_none());
+            if start + invalid.len() == filled && truncated {
+                break;
+            }
+            if !lossy {
+                return Err(io::Error::new(
+                    ErrorKind::InvalidData,
+                    "stream did not contain valid UTF-8",
+                ));
+            }
+            add(char::REPLACEMENT_CHARACTER);
+            start += invalid.len();
+        }
+        buf.copy_within(start..filled, 0);
+        pending = filled - start;
+    }
+    if pending > 0 {
+        if !lossy {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "stream ended in the middle of a UTF-8 sequence",
+            ));
+        }
+        add(char::REPLACEMENT_CHARACTER);
+    }
+    Ok(into_counters(counts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read_counts;
+    use crate::{
+        count_chars_by_lines, count_chars_reader, count_chars_reader_filter,
+        count_chars_reader_lossy, ICharsCounter,
+    };
+    use std::io::{self, BufReader, Cursor, ErrorKind, Read};
+
+    #[test]
+    fn count_chars_reader_test() {
+        let str = "Hello, 世界! Grüße 🦀🦀";
+        assert_eq!(
+            count_chars_reader(Cursor::new(str)).unwrap(),
+            str.count_chars()
+        );
+        assert!(count_chars_reader(Cursor::new("")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn count_chars_reader_boundary_test() {
+        // '世' starts one byte before the first chunk ends.
+        let str = format!("{}世界", "a".repeat(super::CHUNK_SIZE - 1));
+        assert_eq!(
+            count_chars_reader(Cursor::new(&str)).unwrap(),
+            str.count_chars()
+        );
+
+        let str = "a世b🦀c界d";
+        for chunk_size in 4..=8 {
+            assert_eq!(
+                read_counts(Cursor::new(str), chunk_size, false, |_| true).unwrap(),
+                str.count_chars()
+            );
+        }
+    }
+
+    #[test]
+    fn count_chars_reader_invalid_test() {
+        let bytes: &[u8] = b"ab\xffcd";
+        let error = count_chars_reader(Cursor::new(bytes)).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+        let bytes: &[u8] = b"ab\xe4\xb8";
+        let error = count_chars_reader(Cursor::new(bytes)).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+        let failing = Cursor::new("abc").chain(FailingReader);
+        let error = count_chars_reader(failing).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::BrokenPipe);
+    }
+
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::from(ErrorKind::BrokenPipe))
+        }
+    }
+
+    #[test]
+    fn count_chars_reader_filter_test() {
+        let str = "a1世2🦀3b";
+        for chunk_size in 4..=8 {
+            assert_eq!(
+                read_counts(Cursor::new(str), chunk_size, false, |x| !x.is_ascii()).unwrap(),
+                str.count_chars_filter(|x| !x.is_ascii())
+            );
+        }
+        assert_eq!(
+            count_chars_reader_filter(Cursor::new(str), |x| x.is_numeric()).unwrap(),
+            str.count_chars_numeric()
+        );
+    }
+
+    #[test]
+    fn count_chars_reader_lossy_test() {
+        let bytes: &[u8] = b"a\xffb\xe4\xb8c\xe4\xb8\x96\xf0\x9f";
+        let expected = String::from_utf8_lossy(bytes).count_chars();
+        assert_eq!(
+            count_chars_reader_lossy(Cursor::new(bytes)).unwrap(),
+            expected
+        );
+        for chunk_size in 4..=8 {
+            assert_eq!(
+                read_counts(Cursor::new(bytes), chunk_size, true, |_| true).unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn count_chars_by_lines_test() {
+        let str = "Hello\r\nwor\rld\n\n世界";
+        let result = count_chars_by_lines(Cursor::new(str)).unwrap();
+        assert_eq!(result.per_line, str.count_chars_per_line());
+        assert_eq!(result.per_line[1], "wor\rld".count_chars());
+        assert!(result.per_line[2].is_empty());
+        assert_eq!(result.total, str.lines().collect::<String>().count_chars());
+
+        let result = count_chars_by_lines(Cursor::new("")).unwrap();
+        assert!(result.per_line.is_empty());
+        assert!(result.total.is_empty());
+    }
+
+    #[test]
+    fn count_chars_by_lines_error_test() {
+        let failing = BufReader::new(Cursor::new("ab\ncd").chain(FailingReader));
+        let error = count_chars_by_lines(failing).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::BrokenPipe);
+
+        let bytes: &[u8] = b"ab\n\xffcd\n";
+        let error = count_chars_by_lines(Cursor::new(bytes)).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,89 @@
+/// A writing system, matched by the Unicode blocks listed for each variant.
+///
+/// The ranges are block-based approximations of the Unicode `Script` property.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Script {
+    /// ASCII and fullwidth letters, Latin-1 letters, Latin Extended-A to E,
+    /// IPA Extensions and Latin Extended Additional.
+    Latin,
+    /// Cyrillic, Cyrillic Supplement and Cyrillic Extended-A/B/C.
+    Cyrillic,
+    /// Greek and Coptic, Greek Extended.
+    Greek,
+    /// Arabic, Arabic Supplement and Arabic Presentation Forms-A/B.
+    Arabic,
+    /// Hebrew and the Hebrew presentation forms.
+    Hebrew,
+    /// Devanagari and Devanagari Extended.
+    Devanagari,
+    /// CJK Unified Ideographs, their Extensions A to H and the Compatibility Ideographs.
+    /// CJK punctuation such as '，' and '。' is not included.
+    Han,
+    /// Hiragana.
+    Hiragana,
+    /// Katakana and halfwidth Katakana.
+    Katakana,
+    /// Hangul syllables, Jamo and compatibility Jamo.
+    Hangul,
+    /// Thai.
+    Thai,
+}
+
+impl Script {
+    /// The inclusive code point ranges of the script's blocks.
+    pub fn ranges(self) -> &'static [(char, char)] {
+        match self {
+            Script::Latin => &[
+                ('A', 'Z'),
+                ('a', 'z'),
+                ('\u{00C0}', '\u{00D6}'),
+                ('\u{00D8}', '\u{00F6}'),
+                ('\u{00F8}', '\u{02AF}'),
+                ('\u{1E00}', '\u{1EFF}'),
+                ('\u{2C60}', '\u{2C7F}'),
+                ('\u{A720}', '\u{A7FF}'),
+                ('\u{AB30}', '\u{AB6F}'),
+                ('\u{FF21}', '\u{FF3A}'),
+                ('\u{FF41}', '\u{FF5A}'),
+            ],
+            Script::Cyrillic => &[
+                ('\u{0400}', '\u{052F}'),
+                ('\u{1C80}', '\u{1C8F}'),
+                ('\u{2DE0}', '\u{2DFF}'),
+                ('\u{A640}', '\u{A69F}'),
+            ],
+            Script::Greek => &[('\u{0370}', '\u{03FF}'), ('\u{1F00}', '\u{1FFF}')],
+            Script::Arabic => &[
+                ('\u{0600}', '\u{06FF}'),
+                ('\u{0750}', '\u{077F}'),
+                ('\u{FB50}', '\u{FDFF}'),
+                ('\u{FE70}', '\u{FEFF}'),
+            ],
+            Script::Hebrew => &[('\u{0590}', '\u{05FF}'), ('\u{FB1D}', '\u{FB4F}')],
+            Script::Devanagari => &[('\u{0900}', '\u{097F}'), ('\u{A8E0}', '\u{A8FF}')],
+            Script::Han => &[
+                ('\u{3400}', '\u{4DBF}'),
+                ('\u{4E00}', '\u{9FFF}'),
+                ('\u{F900}', '\u{FAFF}'),
+                ('\u{20000}', '\u{2A6DF}'),
+                ('\u{2A700}', '\u{2EBEF}'),
+                ('\u{2F800}', '\u{2FA1F}'),
+                ('\u{30000}', '\u{323AF}'),
+            ],
+            Script::Hiragana => &[('\u{3040}', '\u{309F}')],
+            Script::Katakana => &[('\u{30A0}', '\u{30FF}'), ('\u{FF65}', '\u{FF9F}')],
+            Script::Hangul => &[
+                ('\u{1100}', '\u{11FF}'),
+                ('\u{3130}', '\u{318F}'),
+                ('\u{AC00}', '\u{D7A3}'),
+            ],
+            Script::Thai => &[('\u{0E00}', '\u{0E7F}')],
+        }
+    }
+
+    pub fn contains(self, c: char) -> bool {
+        self.ranges()
+            .iter()
+            .any(|&(start, end)| start <= c && c <= end)
+    }
+}
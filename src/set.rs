@@ -0,0 +1,52 @@
+use crate::Set;
+use alloc::collections::BTreeSet;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+#[cfg(feature = "std")]
+use std::hash::BuildHasher;
+
+/// A set of characters accepted by `count_chars_in_set` and `count_chars_except`.
+pub trait ICharSet {
+    fn contains_char(&self, c: char) -> bool;
+
+    /// A membership test to run once per counted character. A `str` or `[char]` is
+    /// collected into a set here, once per call, instead of being scanned each time.
+    fn lookup(&self) -> impl Fn(char) -> bool + '_ {
+        move |c| self.contains_char(c)
+    }
+}
+
+impl ICharSet for str {
+    fn contains_char(&self, c: char) -> bool {
+        self.contains(c)
+    }
+
+    fn lookup(&self) -> impl Fn(char) -> bool + '_ {
+        let set = self.chars().collect::<Set<_>>();
+        move |c| set.contains(&c)
+    }
+}
+
+impl ICharSet for [char] {
+    fn contains_char(&self, c: char) -> bool {
+        self.contains(&c)
+    }
+
+    fn lookup(&self) -> impl Fn(char) -> bool + '_ {
+        let set = self.iter().copied().collect::<Set<_>>();
+        move |c| set.contains(&c)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: BuildHasher> ICharSet for HashSet<char, S> {
+    fn contains_char(&self, c: char) -> bool {
+        self.contains(&c)
+    }
+}
+
+impl ICharSet for BTreeSet<char> {
+    fn contains_char(&self, c: char) -> bool {
+        self.contains(&c)
+    }
+}
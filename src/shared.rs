@@ -0,0 +1,106 @@
+use crate::{into_counters, CharsCounter, Map};
+use std::sync::{Mutex, PoisonError};
+
+const SHARDS: usize = 16;
+
+/// A counter that many threads can feed at once through a shared reference.
+///
+/// The counts are spread over several locked shards by character, and each `feed`
+/// tallies its text locally first, so it takes every shard lock at most once.
+///
+/// ```
+/// use chars_counter::{ICharsCounter, SharedCharsCounter};
+///
+/// let counter = SharedCharsCounter::new();
+/// std::thread::scope(|s| {
+///     s.spawn(|| counter.feed("Hello "));
+///     s.spawn(|| counter.feed("world!"));
+/// });
+/// assert_eq!(counter.snapshot(), "Hello world!".count_chars());
+/// ```
+pub struct SharedCharsCounter {
+    shards: [Mutex<Map<char, usize>>; SHARDS],
+}
+
+impl Default for SharedCharsCounter {
+    fn default() -> Self {
+        Self {
+            shards: std::array::from_fn(|_| Mutex::new(Map::new())),
+        }
+    }
+}
+
+impl SharedCharsCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn feed(&self, s: &str) {
+        let mut local: [Map<char, usize>; SHARDS] = std::array::from_fn(|_| Map::new());
+        for c in s.chars() {
+            *local[shard_of(c)].entry(c).or_insert(0) += 1;
+        }
+        for (shard, counts) in self.shards.iter().zip(local) {
+            if counts.is_empty() {
+                continue;
+            }
+            let mut shard = shard.lock().unwrap_or_else(PoisonError::into_inner);
+            for (c, n) in counts {
+                *shard.entry(c).or_insert(0) += n;
+            }
+        }
+    }
+
+    /// Returns the counts fed so far, sorted like `count_chars`. A feed running
+    /// concurrently may be only partly included.
+    pub fn snapshot(&self) -> Vec<CharsCounter> {
+        let mut counts = Map::new();
+        for shard in &self.shards {
+            let shard = shard.lock().unwrap_or_else(PoisonError::into_inner);
+            counts.extend(shard.iter().map(|(c, n)| (*c, *n)));
+        }
+        into_counters(counts)
+    }
+}
+
+fn shard_of(c: char) -> usize {
+    c as usize % SHARDS
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ICharsCounter, SharedCharsCounter};
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn feed_threads_test() {
+        let counter = Arc::new(SharedCharsCounter::new());
+        let texts = ["Hello world!", "你好，世界！", "Grüße 🦀", "aaaaaaaaaa"];
+        let handles = texts
+            .into_iter()
+            .map(|text| {
+                let counter = Arc::clone(&counter);
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        counter.feed(text);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(counter.snapshot(), texts.concat().repeat(100).count_chars());
+    }
+
+    #[test]
+    fn snapshot_test() {
+        let counter = SharedCharsCounter::new();
+        assert!(counter.snapshot().is_empty());
+        counter.feed("aab");
+        assert_eq!(counter.snapshot(), "aab".count_chars());
+        counter.feed("b");
+        assert_eq!(counter.snapshot(), "aabb".count_chars());
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{CharsAccumulator, CharsCounter};
+use alloc::vec::Vec;
+use core::future::poll_fn;
+use core::pin::pin;
+use futures_core::Stream;
+
+/// Drains a stream of text chunks and counts them, sorted like `count_chars`.
+///
+/// The chunks are counted as they arrive, so only the counts are kept in memory.
+/// The future is `Send` when the stream and its items are.
+pub async fn count_chars_from_stream<S>(stream: S) -> Vec<CharsCounter>
+where
+    S: Stream,
+    S::Item: AsRef<str>,
+{
+    count_chars_from_stream_filter(stream, |_| true).await
+}
+
+/// Same as `count_chars_from_stream`, counting only the characters accepted by `predicate`.
+/// The future is `Send` when the stream, its items and `predicate` are.
+pub async fn count_chars_from_stream_filter<S, P>(stream: S, predicate: P) -> Vec<CharsCounter>
+where
+    S: Stream,
+    S::Item: AsRef<str>,
+    P: FnMut(&char) -> bool,
+{
+    let mut stream = pin!(stream);
+    let mut acc = CharsAccumulator::with_filter(predicate);
+    while let Some(chunk) = poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+        acc.feed(chunk.as_ref());
+    }
+    acc.finish()
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{count_chars_from_stream, count_chars_from_stream_filter, ICharsCounter};
+    use futures_core::Stream;
+    use std::collections::VecDeque;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    struct Chunks(VecDeque<String>);
+
+    impl Stream for Chunks {
+        type Item = String;
+
+        fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<String>> {
+            Poll::Ready(self.0.pop_front())
+        }
+    }
+
+    fn chunks(texts: &[&str]) -> Chunks {
+        Chunks(texts.iter().map(|x| x.to_string()).collect())
+    }
+
+    #[tokio::test]
+    async fn count_chars_from_stream_test() {
+        let texts = ["Hello, ", "世界", "! 🦀"];
+        let result = count_chars_from_stream(chunks(&texts)).await;
+        assert_eq!(result, texts.concat().count_chars());
+        assert!(count_chars_from_stream(chunks(&[])).await.is_empty());
+
+        // The future can move to another task.
+        let handle = tokio::spawn(count_chars_from_stream(chunks(&texts)));
+        assert_eq!(handle.await.unwrap(), result);
+    }
+
+    #[tokio::test]
+    async fn count_chars_from_stream_filter_test() {
+        let texts = ["a1b", "2½c", "3"];
+        let result = count_chars_from_stream_filter(chunks(&texts), |x| x.is_numeric()).await;
+        assert_eq!(result, texts.concat().count_chars_numeric());
+
+        // A `Send` predicate keeps the future `Send`.
+        let handle = tokio::spawn(count_chars_from_stream_filter(chunks(&texts), |x| {
+            x.is_numeric()
+        }));
+        assert_eq!(handle.await.unwrap(), result);
+
+        // A predicate that is not `Send` can still be used on the current task.
+        let seen = std::rc::Rc::new(std::cell::Cell::new(0));
+        let predicate = |x: &char| {
+            seen.set(seen.get() + 1);
+            x.is_numeric()
+        };
+        let result = count_chars_from_stream_filter(chunks(&texts), predicate).await;
+        assert_eq!(result, texts.concat().count_chars_numeric());
+        assert_eq!(seen.get(), texts.concat().chars().count());
+    }
+}
@@ -0,0 +1,136 @@
+use crate::counter::{cmp_counters, into_sorted};
+use crate::Map;
+use crate::{Counter, ICounterExt};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+#[cfg(feature = "words")]
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The count of one word. Words are compared case-sensitively, so "The" and "the"
+/// are counted separately.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WordCounter {
+    pub word: String,
+    pub count: usize,
+}
+
+impl Counter for WordCounter {
+    type Key = str;
+
+    fn key(&self) -> &str {
+        &self.word
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl Ord for WordCounter {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_counters(self, other)
+    }
+}
+
+impl PartialOrd for WordCounter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub trait IWordsCounter {
+    /// Counts the words separated by whitespace. Punctuation stays attached, so "lazy,"
+    /// and "lazy" are different words.
+    fn count_words(&self) -> Vec<WordCounter>;
+    /// Counts the words found by the Unicode word boundary rules, which leaves out
+    /// punctuation and whitespace.
+    #[cfg(feature = "words")]
+    fn count_words_unicode(&self) -> Vec<WordCounter>;
+}
+
+/// Queries on the result of `count_words`, with `find_by_str` looking a word up by its text.
+pub trait IWordCounterExt {
+    fn most_words(&self) -> Vec<WordCounter>;
+    fn least_words(&self) -> Vec<WordCounter>;
+    fn find_by_str(&self, word: &str) -> Option<WordCounter>;
+}
+
+impl IWordsCounter for str {
+    fn count_words(&self) -> Vec<WordCounter> {
+        count_words_iter(self.split_whitespace())
+    }
+
+    #[cfg(feature = "words")]
+    fn count_words_unicode(&self) -> Vec<WordCounter> {
+        count_words_iter(self.unicode_words())
+    }
+}
+
+fn count_words_iter<'a, I>(words: I) -> Vec<WordCounter>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let mut counts = Map::<&str, usize>::new();
+    for w in words {
+        *counts.entry(w).or_insert(0) += 1;
+    }
+    into_sorted(counts, |word, count| WordCounter {
+        word: word.to_string(),
+        count,
+    })
+}
+
+impl IWordCounterExt for Vec<WordCounter> {
+    fn most_words(&self) -> Vec<WordCounter> {
+        self.most()
+    }
+
+    fn least_words(&self) -> Vec<WordCounter> {
+        self.least()
+    }
+
+    fn find_by_str(&self, word: &str) -> Option<WordCounter> {
+        self.find_by_key(word)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{ICounterExt, IWordCounterExt, IWordsCounter, WordCounter};
+
+    #[test]
+    fn count_words_test() {
+        let str = "the quick the lazy the";
+        let result = str.count_words();
+        assert_eq!(
+            result.most_words(),
+            vec![WordCounter {
+                word: "the".to_string(),
+                count: 3
+            }]
+        );
+        assert_eq!(result.least_words().len(), 2);
+        assert_eq!(result.find_by_num(1)[0].word, "lazy");
+        assert_eq!(result.find_by_str("quick").unwrap().count, 1);
+
+        let result = "The lazy, lazy dog.".count_words();
+        assert_eq!(result.find_by_str("lazy").unwrap().count, 1);
+        assert_eq!(result.find_by_str("lazy,").unwrap().count, 1);
+        assert!(result.find_by_str("the").is_none());
+
+        assert!("".count_words().is_empty());
+        assert!(" \t\n".count_words().most_words().is_empty());
+    }
+
+    #[cfg(feature = "words")]
+    #[test]
+    fn count_words_unicode_test() {
+        let result = "The lazy, lazy dog. Don't!".count_words_unicode();
+        assert_eq!(result.find_by_str("lazy").unwrap().count, 2);
+        assert_eq!(result.find_by_str("Don't").unwrap().count, 1);
+        assert!(result.find_by_str("dog.").is_none());
+        assert!("".count_words_unicode().is_empty());
+    }
+}